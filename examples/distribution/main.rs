@@ -40,7 +40,7 @@ fn standard() -> Result<(), Box<dyn std::error::Error>> {
             &BLACK,
         ))?
         .label("alpha=0.5")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLACK));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK));
 
     chart
         .draw_series(LineSeries::new(
@@ -48,7 +48,7 @@ fn standard() -> Result<(), Box<dyn std::error::Error>> {
             &RED,
         ))?
         .label("alpha=1.00")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
 
 
     chart
@@ -57,7 +57,7 @@ fn standard() -> Result<(), Box<dyn std::error::Error>> {
             &BLUE,
         ))?
         .label("alpha=1.00")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
 
 
     chart
@@ -66,7 +66,7 @@ fn standard() -> Result<(), Box<dyn std::error::Error>> {
             &GREEN,
         ))?
         .label("alpha=1.25")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &GREEN));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN));
 
     chart
         .draw_series(LineSeries::new(
@@ -74,12 +74,12 @@ fn standard() -> Result<(), Box<dyn std::error::Error>> {
             &ORANGE,
         ))?
         .label("alpha=1.50")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &ORANGE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], ORANGE));
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     root.present()?;
@@ -119,7 +119,7 @@ fn normalized() -> Result<(), Box<dyn std::error::Error>> {
             &BLACK,
         ))?
         .label("alpha=0.5")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLACK));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK));
 
     chart
         .draw_series(LineSeries::new(
@@ -127,7 +127,7 @@ fn normalized() -> Result<(), Box<dyn std::error::Error>> {
             &RED,
         ))?
         .label("alpha=1.00")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
 
 
     chart
@@ -136,7 +136,7 @@ fn normalized() -> Result<(), Box<dyn std::error::Error>> {
             &BLUE,
         ))?
         .label("alpha=1.00")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
 
 
     chart
@@ -145,7 +145,7 @@ fn normalized() -> Result<(), Box<dyn std::error::Error>> {
             &GREEN,
         ))?
         .label("alpha=1.25")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &GREEN));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN));
 
     chart
         .draw_series(LineSeries::new(
@@ -153,12 +153,12 @@ fn normalized() -> Result<(), Box<dyn std::error::Error>> {
             &ORANGE,
         ))?
         .label("alpha=1.50")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &ORANGE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], ORANGE));
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     root.present()?;
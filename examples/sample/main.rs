@@ -1,4 +1,4 @@
-use alpha_stable::AlphaStable;
+use alpha_stable::LevyStableMotion;
 
 use rand::thread_rng;
 
@@ -9,30 +9,26 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let n_sets = 10;
     let n_samples = 1000;
 
-    plot_sample_sets(n_sets, n_samples, "Alpha(alpha: 1.1, beta: 0.0, sigma: 1.0, mu: 0.0)", "Samples_1.png", &AlphaStable::new(1.1, 0., 1.0, 0.0)?)?;
-    plot_sample_sets(n_sets, n_samples, "Alpha(alpha: 1.5, beta: 0.0, sigma: 1.0, mu: 0.0)", "Samples_2.png", &AlphaStable::new(1.5, 0., 1.0, 0.0)?)?;
-    plot_sample_sets(n_sets, n_samples, "Alpha(alpha: 2.0, beta: 0.0, sigma: 1.0, mu: 0.0)", "Samples_3.png", &AlphaStable::new(2.0, 0., 1.0, 0.0)?)?;
+    plot_sample_sets(n_sets, n_samples, "Alpha(alpha: 1.1, beta: 0.0, sigma: 1.0, mu: 0.0)", "Samples_1.png", &LevyStableMotion::new(1.1, 0., 1.0, 0.0)?)?;
+    plot_sample_sets(n_sets, n_samples, "Alpha(alpha: 1.5, beta: 0.0, sigma: 1.0, mu: 0.0)", "Samples_2.png", &LevyStableMotion::new(1.5, 0., 1.0, 0.0)?)?;
+    plot_sample_sets(n_sets, n_samples, "Alpha(alpha: 2.0, beta: 0.0, sigma: 1.0, mu: 0.0)", "Samples_3.png", &LevyStableMotion::new(2.0, 0., 1.0, 0.0)?)?;
 
     Ok(())
 }
 
-fn plot_sample_sets(n_sets: u32, n_samples: u32, title: &str, filename: &str, alpha_stable: &AlphaStable) -> Result<(), Box<dyn std::error::Error>> {
+fn plot_sample_sets(n_sets: u32, n_samples: u32, title: &str, filename: &str, process: &LevyStableMotion) -> Result<(), Box<dyn std::error::Error>> {
 
     let mut rng = thread_rng();
+    let times: Vec<f64> = (0..n_samples).map(|t| t as f64).collect();
     let mut sample_sets = Vec::new();
     let mut ymin = Vec::new();
     let mut ymax = Vec::new();
-    let colors = vec![&BLACK, &RED, &BLUE, &GREEN, &ORANGE];
+    let colors = [&BLACK, &RED, &BLUE, &GREEN, &ORANGE];
 
-    for _ in 0..n_sets {
-        let mut sum = 0.0;
-        let mut samples = Vec::new();
-        for _ in 0..n_samples {
-            samples.push(sum);
-            sum += alpha_stable.sample(&mut rng);
-        }
-        let yminf = *samples.iter().min_by(|a, b| a.total_cmp(&b)).unwrap();
-        let ymaxf = *samples.iter().max_by(|a, b| a.total_cmp(&b)).unwrap();
+    for path in process.sample_paths(&times, n_sets as usize, &mut rng)? {
+        let samples = path.get_values().to_vec();
+        let yminf = *samples.iter().min_by(|a, b| a.total_cmp(b)).unwrap();
+        let ymaxf = *samples.iter().max_by(|a, b| a.total_cmp(b)).unwrap();
 
         sample_sets.push(samples);
         ymin.push(yminf); 
@@ -41,8 +37,8 @@ fn plot_sample_sets(n_sets: u32, n_samples: u32, title: &str, filename: &str, al
 
     let xminf = 0.0;  
     let xmaxf = n_samples as f64;  
-    let yminf = *ymin.iter().min_by(|a, b| a.total_cmp(&b)).unwrap();
-    let ymaxf = *ymax.iter().max_by(|a, b| a.total_cmp(&b)).unwrap();
+    let yminf = *ymin.iter().min_by(|a, b| a.total_cmp(b)).unwrap();
+    let ymaxf = *ymax.iter().max_by(|a, b| a.total_cmp(b)).unwrap();
 
     let root = BitMapBackend::new(filename, (1024, 768)).into_drawing_area();
     root.fill(&WHITE)?;
@@ -69,7 +69,7 @@ fn plot_histogram(n_samples: u32, title: &str, filename: &str, alpha_stable: &Al
     chart
         .draw_series(pdf)?
         .label("PDF")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
 
     chart.configure_series_labels().draw()?;
 
@@ -1,14 +1,19 @@
+use std::cell::Cell;
 use std::f64::consts::PI;
+use std::ops::{Add, Mul};
 
+use num_complex::Complex64;
 use rand::Rng;
 use rand_distr::{Exp1, Distribution};
 use spfunc::gamma::gamma;
 
+use crate::bisect::bisect;
 use crate::integrator::Integrator;
 use crate::error::Error;
+use crate::special::{erfc, normal_quantile};
 
 /// Defines an Alpha Stable distribution in Standard or Nolan's form.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AlphaStable {
     alpha: f64,
     beta: f64,
@@ -34,7 +39,7 @@ impl AlphaStable {
             return Err(Error::AlphaError {alpha});
         }
 
-        if beta < -1.0 || beta > 1.0 {
+        if !(-1.0..=1.0).contains(&beta) {
             return Err(Error::BetaError {beta});    
         }
 
@@ -57,7 +62,7 @@ impl AlphaStable {
             return Err(Error::AlphaError {alpha});
         }
 
-        if beta < -1.0 || beta > 1.0 {
+        if !(-1.0..=1.0).contains(&beta) {
             return Err(Error::BetaError {beta});    
         }
 
@@ -89,8 +94,38 @@ impl AlphaStable {
         (self.alpha, self.beta, self.sigma, self.mu, self.mu_0)
     }
 
+    pub(crate) fn get_tol(&self) -> &Tol {
+        &self.tol
+    }
+
+    /// Return the support of the distribution as tuple of (lower, upper).
+    ///
+    /// Totally skewed distributions with alpha < 1 are supported on a half-line that starts at mu; all others on the whole real line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 0.5, 1.0, 1.0, 2.0).unwrap();
+    /// assert_eq!(distribution.support(), (2.0, f64::INFINITY));
+    /// ```
+    pub fn support(&self) -> (f64, f64) {
+        if !self.is_one_sided() {
+            (f64::NEG_INFINITY, f64::INFINITY)
+        } else if self.beta > 0.0 {
+            (self.mu, f64::INFINITY)
+        } else {
+            (f64::NEG_INFINITY, self.mu)
+        }
+    }
+
+    fn is_one_sided(&self) -> bool {
+        self.alpha < 1.0 && !close( self.alpha, 1.0, self.tol.alpha ) && close( self.beta.abs(), 1.0, self.tol.beta )
+    }
+
     /// Sample from the distribution.
     /// 
+    /// Totally skewed distributions with alpha < 1 are sampled with Kanter's method; all others with the method of Chambers, Mallows and Stuck.
+    /// 
     /// # Example
     /// 
     /// ```
@@ -106,6 +141,10 @@ impl AlphaStable {
             return self.sample_symmetric(rng);
         }
 
+        if self.is_one_sided() {
+            return self.sample_positive(rng);
+        }
+
         let v = PI * (rng.gen::<f64>() - 0.5);
         
         let mut w = 0.0;
@@ -125,7 +164,25 @@ impl AlphaStable {
         let x = s * ( self.alpha * (v + b) ).sin() * 
                 ( ( (v - self.alpha*(v + b)).cos() / w ).powf((1.0 - self.alpha) / self.alpha) )  / 
                 ( v.cos().powf(1.0 / self.alpha) );
-        return self.sigma * x + self.mu;
+        self.sigma * x + self.mu
+    }
+
+    // Kanter's representation of a positive stable variable S with E[exp(-sS)] = exp(-s^alpha), scaled to S_alpha(sigma, +-1, mu).
+    fn sample_positive<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let mut u = 0.0_f64;
+        while u == 0.0 {
+            u = rng.gen::<f64>();
+        }
+
+        let mut w = 0.0_f64;
+        while w == 0.0 {
+            w = Exp1.sample(rng); 
+        }
+
+        let a = self.alpha;
+        let s = (a * PI * u).sin() / (PI * u).sin().powf(1.0 / a) * ( ((1.0 - a) * PI * u).sin() / w ).powf( (1.0 - a) / a );
+        let scale = self.sigma / (0.5 * PI * a).cos().powf(1.0 / a);
+        self.beta.signum() * scale * s + self.mu
     }
 
     fn sample_symmetric<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
@@ -161,10 +218,344 @@ impl AlphaStable {
         let x = (x - self.mu_0) / self.sigma;
         let val = pdf_scaled(x, self.alpha, self.beta, &self.tol, &self.integrator)?;
         Ok(val/self.sigma)
-    }                
+    }
+
+    /// Derivative of Probability Distribution function at x.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let val = distribution.pdf_derivative( 0.5 ).unwrap();
+    /// assert!(val < 0.0);
+    /// ```
+    pub fn pdf_derivative(&self, x: f64) -> Result<f64, Error> {
+        let x = (x - self.mu_0) / self.sigma;
+        let (_, gradient) = pdf_gradient_scaled(x, self.alpha, self.beta, &self.tol, &self.integrator)?;
+        Ok(gradient[2] / (self.sigma * self.sigma))
+    }
+
+    /// Score at x: the gradient of the logarithm of Probability Distribution function with respect to (alpha, beta, sigma, mu)
+    /// of Standard form. Values are not finite where the density vanishes.
+    ///
+    /// Derivatives are found by differentiating under the integral for the density, or by finite differences where
+    /// alpha, beta or x are within the tolerances of special cases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let score = distribution.score( 0.5 ).unwrap();
+    /// assert!(score[3] > 0.0);
+    /// ```
+    pub fn score(&self, x: f64) -> Result<[f64; 4], Error> {
+        let (_, mut score) = self.score_S0(x)?;
+        let gradient = self.mu_0_gradient();
+        for i in 0..3 {
+            score[i] += gradient[i] * score[3];
+        }
+        Ok(score)
+    }
 
+    // Density at x and the gradient of its logarithm with respect to (alpha, beta, sigma, mu_0) of Nolan's form.
+    #[allow(non_snake_case)]
+    pub(crate) fn score_S0(&self, x: f64) -> Result<(f64, [f64; 4]), Error> {
+        let z = (x - self.mu_0) / self.sigma;
+        let (f, [f_alpha, f_beta, f_z]) = pdf_gradient_scaled(z, self.alpha, self.beta, &self.tol, &self.integrator)?;
+        let score = [f_alpha / f, f_beta / f, -(1.0 + z * f_z / f) / self.sigma, -f_z / (f * self.sigma)];
+        Ok((f / self.sigma, score))
+    }
+
+    // Gradient of mu_0 with respect to (alpha, beta, sigma, mu) of Standard form. The derivative with respect to alpha is
+    // taken as 0 when alpha == 1, where Standard form is discontinuous.
+    pub(crate) fn mu_0_gradient(&self) -> [f64; 4] {
+        let (alpha, beta, sigma) = (self.alpha, self.beta, self.sigma);
+        if close(alpha, 1.0, self.tol.alpha) {
+            [0.0, 2.0 * sigma * sigma.ln() / PI, 2.0 * beta * (sigma.ln() + 1.0) / PI, 1.0]
+        } else {
+            let tan = (0.5 * PI * alpha).tan();
+            [0.5 * PI * beta * sigma * (1.0 + tan * tan), sigma * tan, beta * tan, 1.0]
+        }
+    }
+
+    /// Value of Cumulative Distribution function at x.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let val = distribution.cdf( 0.5 ).unwrap();
+    /// ```
+    pub fn cdf(&self, x: f64) -> Result<f64, Error> {
+        let x = (x - self.mu_0) / self.sigma;
+        let val = cdf_scaled(x, self.alpha, self.beta, &self.tol, &self.integrator)?;
+        Ok(val.clamp(0.0, 1.0))
+    }
+
+    /// Quantile function: the value of x at which the Cumulative Distribution function equals p, found by bisection.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let median = distribution.quantile( 0.5 ).unwrap();
+    /// ```
+    pub fn quantile(&self, p: f64) -> Result<f64, Error> {
+
+        if p <= 0.0 || p >= 1.0 || p.is_nan() {
+            return Err(Error::ProbabilityError { p });
+        }
+
+        // Expand a bracket around mu_0 until it contains the quantile
+        let mut width = self.sigma;
+        let mut lower = self.mu_0 - width;
+        let mut upper = self.mu_0 + width;
+        let mut n = 0;
+        while self.cdf(lower)? > p || self.cdf(upper)? < p {
+            if n == QUANTILE_BRACKET_LIMIT {
+                return Err(Error::BisectionRangeError { a: lower, b: upper });
+            }
+            width *= 2.0;
+            lower = self.mu_0 - width;
+            upper = self.mu_0 + width;
+            n += 1;
+        }
+
+        let error = Cell::new(None);
+        let f = |x: f64| {
+            match self.cdf(x) {
+                Ok(val) => val - p,
+                Err(e) => {
+                    error.set(Some(e));
+                    0.0
+                }
+            }
+        };
+        let result = bisect(&f, lower, upper, QUANTILE_EPS * width, QUANTILE_ITERATIONS);
+        if let Some(e) = error.get() {
+            return Err(e);
+        }
+        result.estimate()
+    }
+
+    /// Value-at-Risk at tail probability level: the loss -x that returns x drawn from the distribution fall below with
+    /// probability level, such as 0.01 or 0.05.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 1.7, 0.0, 0.01, 0.0).unwrap();
+    /// let var = distribution.value_at_risk( 0.05 ).unwrap();
+    /// ```
+    pub fn value_at_risk(&self, level: f64) -> Result<f64, Error> {
+        Ok(-self.quantile(level)?)
+    }
+
+    /// Expected Shortfall at tail probability level: the expected loss -x given that returns x fall below minus the
+    /// Value-at-Risk, ES = -(1 / level) * integral of the quantile function over (0, level).
+    ///
+    /// Uses the integral representation of Stoyanov, Samorodnitsky, Rachev and Ortobelli, Computing the portfolio
+    /// conditional value-at-risk in the alpha-stable case, Probability and Mathematical Statistics 26 (2006). Expected
+    /// Shortfall is finite only for alpha > 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 1.7, 0.0, 0.01, 0.0).unwrap();
+    /// let es = distribution.expected_shortfall( 0.05 ).unwrap();
+    /// assert!(es > distribution.value_at_risk( 0.05 ).unwrap());
+    /// ```
+    pub fn expected_shortfall(&self, level: f64) -> Result<f64, Error> {
+
+        if level <= 0.0 || level >= 1.0 || level.is_nan() {
+            return Err(Error::ProbabilityError { p: level });
+        }
+        if self.alpha <= 1.0 || close( self.alpha, 1.0, self.tol.alpha ) {
+            return Err(Error::InfiniteExpectedShortfallError { alpha: self.alpha });
+        }
+
+        if close( self.alpha, 2.0, self.tol.alpha ) {
+            // Normal distribution with variance 2 sigma^2
+            let z = normal_quantile(level);
+            return Ok(self.sigma * (-0.5 * z * z).exp() / (level * PI.sqrt()) - self.mu);
+        }
+
+        // Expected Shortfall of the standard form distribution with unit scale and zero location
+        let var = (self.value_at_risk(level)? + self.mu) / self.sigma;
+        let alpha = self.alpha;
+        let es = if var.abs() < ES_VAR_EPS {
+            let theta_0 = (self.beta * (0.5 * PI * alpha).tan()).atan() / alpha;
+            2.0 * gamma((alpha - 1.0) / alpha) * theta_0.cos() / ((PI - 2.0 * theta_0) * (alpha * theta_0).cos().powf(1.0 / alpha))
+        } else {
+            let beta = -var.signum() * self.beta;
+            let theta_0 = (beta * (0.5 * PI * alpha).tan()).atan() / alpha;
+            let log_var = alpha / (alpha - 1.0) * var.abs().ln();
+            let g = |theta: f64| {
+                let s = (alpha * (theta_0 + theta)).sin();
+                let log_v = (alpha * theta_0).cos().ln() / (alpha - 1.0)
+                    + alpha / (alpha - 1.0) * (theta.cos() / s).ln()
+                    + (alpha * theta_0 + (alpha - 1.0) * theta).cos().ln()
+                    - theta.cos().ln();
+                let weight = (-(log_var + log_v).exp()).exp();
+                if weight == 0.0 {
+                    return 0.0;
+                }
+                weight * ((alpha * (theta_0 + theta) - 2.0 * theta).sin() / s - alpha * theta.cos().powi(2) / (s * s))
+            };
+            let val = self.integrator.integrate_range(&g, -theta_0, 0.5 * PI)?;
+            alpha / (1.0 - alpha) * var.abs() * val / (PI * level)
+        };
+
+        Ok(self.sigma * es - self.mu)
+    }
+
+    /// Characteristic function E[exp(itX)].
+    ///
+    /// Computed from the parameters of Nolan's form, which give the same function as those of Standard form and are
+    /// continuous in alpha: ln phi(t) = -sigma^alpha |t|^alpha [1 + i beta tan(pi alpha / 2) sign(t) (|sigma t|^(1 - alpha) - 1)] + i mu_0 t,
+    /// or -sigma |t| [1 + i beta (2 / pi) sign(t) ln(sigma |t|)] + i mu_0 t when alpha == 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 1.0, 0.0, 1.0, 0.0).unwrap();
+    /// let phi = distribution.characteristic_function( 2.0 );
+    /// assert!((phi.re - (-2.0_f64).exp()).abs() < 1e-15);
+    /// ```
+    pub fn characteristic_function(&self, t: f64) -> Complex64 {
+        self.log_characteristic_function(t).exp()
+    }
+
+    fn log_characteristic_function(&self, t: f64) -> Complex64 {
+        if t == 0.0 {
+            return Complex64::new(0.0, 0.0);
+        }
+        let u = self.sigma * t.abs();
+        let skew = if close( self.alpha, 1.0, self.tol.alpha ) {
+            -self.beta * u * 2.0 * u.ln() / PI
+        } else {
+            self.beta * (0.5 * PI * self.alpha).tan() * (u.powf(self.alpha) - u)
+        };
+        Complex64::new(-u.powf(self.alpha), t.signum() * skew + self.mu_0 * t)
+    }
+
+    /// Distribution of the sum X + Y of independent X and Y drawn from this distribution and other, which is alpha stable
+    /// when both share alpha.
+    ///
+    /// In Standard form sigma^alpha = sigma_X^alpha + sigma_Y^alpha, beta sigma^alpha = beta_X sigma_X^alpha + beta_Y sigma_Y^alpha
+    /// and mu = mu_X + mu_Y for all alpha. The operator + gives the same result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::AlphaStable;
+    ///
+    /// let x = AlphaStable::new( 1.5, 1.0, 1.0, 0.0).unwrap();
+    /// let y = AlphaStable::new( 1.5, -1.0, 1.0, 2.0).unwrap();
+    /// let (_, beta, _, mu, _) = x.convolve( &y ).unwrap().get_params();
+    /// assert_eq!((beta, mu), (0.0, 2.0));
+    /// ```
+    pub fn convolve(&self, other: &AlphaStable) -> Result<AlphaStable, Error> {
+
+        if !close( self.alpha, other.alpha, self.tol.alpha ) {
+            return Err(Error::AlphaMismatchError { alpha_1: self.alpha, alpha_2: other.alpha });
+        }
+
+        let (weight, other_weight) = (self.sigma.powf(self.alpha), other.sigma.powf(self.alpha));
+        let beta = (self.beta * weight + other.beta * other_weight) / (weight + other_weight);
+        self.with_params(beta.clamp(-1.0, 1.0), (weight + other_weight).powf(1.0 / self.alpha), self.mu + other.mu)
+    }
+
+    /// Distribution of cX for X drawn from this distribution and c != 0.
+    ///
+    /// In Standard form sigma scales by |c|, beta by sign(c) and mu by c, less (2 / pi) c ln|c| sigma beta when
+    /// alpha == 1. The operator * gives the same result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let distribution = alpha_stable::AlphaStable::new( 1.0, 0.5, 1.0, 0.0).unwrap();
+    /// let (_, beta, sigma, _, _) = distribution.scale( -2.0 ).unwrap().get_params();
+    /// assert_eq!((beta, sigma), (-0.5, 2.0));
+    /// ```
+    pub fn scale(&self, c: f64) -> Result<AlphaStable, Error> {
+
+        if c == 0.0 || !c.is_finite() {
+            return Err(Error::ScaleError { scale: c });
+        }
+
+        let mu = if close( self.alpha, 1.0, self.tol.alpha ) {
+            c * self.mu - 2.0 * c * c.abs().ln() * self.sigma * self.beta / PI
+        } else {
+            c * self.mu
+        };
+        self.with_params(c.signum() * self.beta, c.abs() * self.sigma, mu)
+    }
+
+    /// Distribution of X + d for X drawn from this distribution, with both mu and mu_0 shifted by d.
+    pub fn shift(&self, d: f64) -> AlphaStable {
+        AlphaStable { mu: self.mu + d, mu_0: self.mu_0 + d, ..self.clone() }
+    }
+
+    /// Distribution of -X for X drawn from this distribution, with beta, mu and mu_0 negated.
+    pub fn negate(&self) -> AlphaStable {
+        AlphaStable { beta: -self.beta, mu: -self.mu, mu_0: -self.mu_0, ..self.clone() }
+    }
+
+    // Distribution with the same alpha, tolerances and integrator, and the given Standard form parameters.
+    fn with_params(&self, beta: f64, sigma: f64, mu: f64) -> Result<AlphaStable, Error> {
+        let mut distribution = AlphaStable::new(self.alpha, beta, sigma, mu)?;
+        distribution.with_tol(self.tol.clone()).with_integrator(self.integrator.clone());
+        Ok(distribution)
+    }
+
+}
+
+impl Add for &AlphaStable {
+    type Output = Result<AlphaStable, Error>;
+
+    fn add(self, other: &AlphaStable) -> Self::Output {
+        self.convolve(other)
+    }
+}
+
+impl Add for AlphaStable {
+    type Output = Result<AlphaStable, Error>;
+
+    fn add(self, other: AlphaStable) -> Self::Output {
+        self.convolve(&other)
+    }
 }
 
+impl Mul<f64> for &AlphaStable {
+    type Output = Result<AlphaStable, Error>;
+
+    fn mul(self, c: f64) -> Self::Output {
+        self.scale(c)
+    }
+}
+
+impl Mul<f64> for AlphaStable {
+    type Output = Result<AlphaStable, Error>;
+
+    fn mul(self, c: f64) -> Self::Output {
+        self.scale(c)
+    }
+}
+
+// Maximum number of times the initial bracket is doubled when searching for a quantile.
+const QUANTILE_BRACKET_LIMIT: u32 = 100;
+
+// Convergence tolerance of quantile bisection relative to the width of the bracket.
+const QUANTILE_EPS: f64 = 1e-12;
+
+// Maximum number of bisection iterations used to find a quantile.
+const QUANTILE_ITERATIONS: u64 = 200;
+
+// Below this Value-at-Risk of the standard distribution, Expected Shortfall takes its value at zero Value-at-Risk, where
+// the integral representation is singular.
+const ES_VAR_EPS: f64 = 1e-9;
+
 // Calculates pdf by direct integration as described on page 7 of paper.
 fn pdf_scaled(x: f64, alpha: f64, beta: f64, tol: &Tol, integrator: &Integrator) -> Result<f64, Error> {
 
@@ -173,24 +564,33 @@ fn pdf_scaled(x: f64, alpha: f64, beta: f64, tol: &Tol, integrator: &Integrator)
         // Normal distribution
         return Ok((-0.25 * x * x).exp() / (4.0 * PI).sqrt());
 
+    } else if close( alpha, 0.5, tol.alpha) && close(beta.abs(), 1.0, tol.beta) {
+
+        // Levy distribution: alpha == 1/2, beta == +-1, located at zeta = -beta
+        let z = beta.signum() * x + 1.0;
+        if z <= 0.0 {
+            return Ok(0.0);
+        }
+        return Ok((-0.5 / z).exp() / ((2.0 * PI).sqrt() * z.powf(1.5)));
+
     } else if close( alpha, 1.0, tol.alpha) && !close(beta, 0.0, tol.beta) {
 
-        // alpha == 1, beta != 0
-        let gamma = (-0.5 * PI * x / beta).exp();
+        // alpha == 1, beta != 0. The integrand is formed from ln(gamma V) to avoid overflow in the tails
+        let log_gamma = -0.5 * PI * x / beta;
         let a = -0.5 * PI;
         let b =  0.5 * PI;
 
         let val = integrator.integrate(
                 &|theta| {
-                    derivative_alpha_eq_1(theta, beta) * gamma - 1.0
+                    log_derivative_alpha_eq_1(theta, beta) + log_gamma
                 },
                 &|theta| {
-                    integrand_alpha_eq_1(theta, beta, gamma)
+                    integrand(log_derivative_alpha_eq_1(theta, beta) + log_gamma)
                 },
                 a, b,
         )?;
 
-        return Ok(val * 0.5 * gamma / beta.abs());
+        return Ok(val * 0.5 / beta.abs());
 
     } else if close(alpha, 1.0, tol.alpha) && close(beta, 0.0, tol.beta) {
         
@@ -203,36 +603,266 @@ fn pdf_scaled(x: f64, alpha: f64, beta: f64, tol: &Tol, integrator: &Integrator)
         let zeta = -beta * (0.5 * PI * alpha).tan();
         let eps = (-zeta).atan() / alpha;
 
-        if close(x, zeta, tol.zeta) {
+        if outside_support(x, zeta, alpha, beta, tol) {
+
+            // Totally skewed with alpha < 1
+            return Ok(0.0);
+
+        } else if close(x, zeta, tol.zeta) {
 
             // Special case x = zeta
             return Ok(gamma(1.0 + 1.0 / alpha) * eps.cos() / (PI * (1.0 + zeta * zeta).powf(0.5 / alpha)));
         
         } else if x > zeta {
 
-            // x > zeta
-            let gamma = (x - zeta).powf(alpha / (alpha - 1.0));
+            // x > zeta. The integrand is formed from ln(gamma V) to avoid overflow in the tails
+            let log_gamma = alpha / (alpha - 1.0) * (x - zeta).ln();
             let a = -eps;
             let b = 0.5 * PI;
 
             let val = integrator.integrate(
                 &|theta| {
-                    derivative_alpha_neq_1(theta, alpha, eps) * gamma - 1.0
+                    log_derivative_alpha_neq_1(theta, alpha, eps) + log_gamma
                 },
                 &|theta| {
-                    integrand_alpha_neq_1(theta, alpha, eps, gamma)
+                    integrand(log_derivative_alpha_neq_1(theta, alpha, eps) + log_gamma)
                 },
                 a, b,
             )?;
 
-            return Ok(val * alpha * (x - zeta).powf(1.0/(alpha - 1.0)) / (PI * (alpha - 1.0).abs()));
+            return Ok(val * alpha / (PI * (alpha - 1.0).abs() * (x - zeta)));
 
         } else if x < zeta {
             // symmetric case
             return pdf_scaled(-x, alpha, -beta, tol, integrator);
         }
     }
-    return Ok(0.0);
+    Ok(0.0)
+}
+
+// Calculates pdf with its partial derivatives with respect to (alpha, beta, x), by differentiating under the integral of
+// pdf_scaled. Where pdf_scaled takes a special case the derivatives that it does not give in closed form are found by
+// finite differences.
+fn pdf_gradient_scaled(x: f64, alpha: f64, beta: f64, tol: &Tol, integrator: &Integrator) -> Result<(f64, [f64; 3]), Error> {
+
+    // alpha is kept positive by a lower bound of half its value
+    let f_alpha = || difference(&|a| pdf_scaled(x, a, beta, tol, integrator), alpha, 0.5 * alpha, 2.0);
+    let f_beta = || difference(&|b| pdf_scaled(x, alpha, b, tol, integrator), beta, -1.0, 1.0);
+
+    if close( alpha, 2.0, tol.alpha) {
+
+        // Normal distribution, on which beta has no effect
+        let f = pdf_scaled(x, alpha, beta, tol, integrator)?;
+        Ok((f, [f_alpha()?, 0.0, -0.5 * x * f]))
+
+    } else if close( alpha, 0.5, tol.alpha) && close(beta.abs(), 1.0, tol.beta) {
+
+        // Levy distribution
+        let f = pdf_scaled(x, alpha, beta, tol, integrator)?;
+        let z = beta.signum() * x + 1.0;
+        let f_x = if z <= 0.0 { 0.0 } else { beta.signum() * f * (0.5 / z - 1.5) / z };
+        Ok((f, [f_alpha()?, f_beta()?, f_x]))
+
+    } else if close( alpha, 1.0, tol.alpha) && !close(beta, 0.0, tol.beta) {
+
+        // alpha == 1, beta != 0
+        let log_gamma = -0.5 * PI * x / beta;
+        let log_product = |theta: f64| log_derivative_alpha_eq_1(theta, beta) + log_gamma;
+        let (a, b) = (-0.5 * PI, 0.5 * PI);
+
+        let val = integrator.integrate(&log_product, &|theta| integrand(log_product(theta)), a, b)?;
+        let val_x = integrator.integrate_signed(&log_product, &|theta| integrand_derivative(log_product(theta)), a, b)?;
+        let val_beta = integrator.integrate_signed(
+            &log_product,
+            &|theta| {
+                let d_beta = 0.5 * PI * x / (beta * beta) + (2.0 * theta / PI) / (1.0 + 2.0 * beta * theta / PI)
+                    - 0.5 * PI * theta.tan() / (beta * beta);
+                finite_or_zero(integrand_derivative(log_product(theta)) * d_beta)
+            },
+            a, b,
+        )?;
+
+        let c = 0.5 / beta.abs();
+        Ok((c * val, [f_alpha()?, c * (val_beta - val / beta), -c * 0.5 * PI * val_x / beta]))
+
+    } else if close( alpha, 1.0, tol.alpha) {
+
+        // Cauchy distribution
+        let f = pdf_scaled(x, alpha, beta, tol, integrator)?;
+        Ok((f, [f_alpha()?, f_beta()?, -2.0 * PI * x * f * f]))
+
+    } else {
+
+        // alpha != 1 cases
+        let zeta = -beta * (0.5 * PI * alpha).tan();
+
+        if outside_support(x, zeta, alpha, beta, tol) {
+
+            // Totally skewed with alpha < 1
+            Ok((0.0, [0.0; 3]))
+
+        } else if close(x, zeta, tol.zeta) {
+
+            // Special case x = zeta
+            let f = pdf_scaled(x, alpha, beta, tol, integrator)?;
+            let f_x = difference(&|y| pdf_scaled(y, alpha, beta, tol, integrator), x, f64::NEG_INFINITY, f64::INFINITY)?;
+            Ok((f, [f_alpha()?, f_beta()?, f_x]))
+
+        } else if x > zeta {
+
+            // x > zeta. With f = c I, where I is the integral of gamma V exp(-gamma V), each derivative is
+            // f (ln c)' + c times the integral of (1 - gamma V) gamma V exp(-gamma V) (ln gamma V)'
+            let tan = (0.5 * PI * alpha).tan();
+            let eps = (-zeta).atan() / alpha;
+            let (zeta_alpha, zeta_beta) = (-0.5 * PI * beta * (1.0 + tan * tan), -tan);
+            let eps_alpha = -zeta_alpha / ((1.0 + zeta * zeta) * alpha) - eps / alpha;
+            let eps_beta = -zeta_beta / ((1.0 + zeta * zeta) * alpha);
+
+            let d = x - zeta;
+            let k = alpha / (alpha - 1.0);
+            let log_gamma = k * d.ln();
+            let log_product = |theta: f64| log_derivative_alpha_neq_1(theta, alpha, eps) + log_gamma;
+            let (a, b) = (-eps, 0.5 * PI);
+
+            let val = integrator.integrate(&log_product, &|theta| integrand(log_product(theta)), a, b)?;
+            let val_x = integrator.integrate_signed(&log_product, &|theta| integrand_derivative(log_product(theta)), a, b)?;
+            let mut val_params = [0.0; 2];
+            for (i, val_param) in val_params.iter_mut().enumerate() {
+                *val_param = integrator.integrate_signed(
+                    &log_product,
+                    &|theta| {
+                        let derivative = integrand_derivative(log_product(theta));
+                        if derivative == 0.0 {
+                            return 0.0;
+                        }
+                        let (v_alpha, v_eps) = log_derivative_alpha_neq_1_gradient(theta, alpha, eps);
+                        let d_param = if i == 0 {
+                            -d.ln() / ((alpha - 1.0) * (alpha - 1.0)) - k * zeta_alpha / d + v_alpha + v_eps * eps_alpha
+                        } else {
+                            -k * zeta_beta / d + v_eps * eps_beta
+                        };
+                        finite_or_zero(derivative * d_param)
+                    },
+                    a, b,
+                )?;
+            }
+
+            let c = alpha / (PI * (alpha - 1.0).abs() * d);
+            let f = c * val;
+            let f_alpha = f * (1.0 / alpha - 1.0 / (alpha - 1.0) + zeta_alpha / d) + c * val_params[0];
+            let f_beta = f * zeta_beta / d + c * val_params[1];
+            let f_x = -f / d + c * k * val_x / d;
+            Ok((f, [f_alpha, f_beta, f_x]))
+
+        } else {
+
+            // symmetric case
+            let (f, [f_alpha, f_beta, f_x]) = pdf_gradient_scaled(-x, alpha, -beta, tol, integrator)?;
+            Ok((f, [f_alpha, -f_beta, -f_x]))
+        }
+    }
+}
+
+// Step of finite differences of pdf_scaled, relative to the magnitude of the variable where that exceeds 1.
+const DIFFERENCE_STEP: f64 = 1e-4;
+
+// Central difference of g at v, one sided where v lies within a step of lower or upper.
+fn difference(g: &dyn Fn(f64) -> Result<f64, Error>, v: f64, lower: f64, upper: f64) -> Result<f64, Error> {
+    let h = DIFFERENCE_STEP * v.abs().max(1.0);
+    let (a, b) = ((v - h).max(lower), (v + h).min(upper));
+    Ok((g(b)? - g(a)?) / (b - a))
+}
+
+// Calculates cdf by direct integration as described by Nolan (1997).
+fn cdf_scaled(x: f64, alpha: f64, beta: f64, tol: &Tol, integrator: &Integrator) -> Result<f64, Error> {
+
+    if close( alpha, 2.0, tol.alpha) {
+
+        // Normal distribution with variance 2
+        Ok(0.5 * erfc(-0.5 * x))
+
+    } else if close( alpha, 0.5, tol.alpha) && close(beta.abs(), 1.0, tol.beta) {
+
+        // Levy distribution: alpha == 1/2, beta == +-1, located at zeta = -beta
+        let z = beta.signum() * x + 1.0;
+        let val = if z <= 0.0 { 0.0 } else { erfc((0.5 / z).sqrt()) };
+        Ok(if beta > 0.0 { val } else { 1.0 - val })
+
+    } else if close( alpha, 1.0, tol.alpha) && close(beta, 0.0, tol.beta) {
+
+        // Cauchy distribution: alpha == 1, beta == 0
+        Ok(0.5 + x.atan() / PI)
+
+    } else if close( alpha, 1.0, tol.alpha) {
+
+        // alpha == 1, beta != 0
+        if beta < 0.0 {
+            return Ok(1.0 - cdf_scaled(-x, alpha, -beta, tol, integrator)?);
+        }
+        let log_gamma = -0.5 * PI * x / beta;
+
+        // Split integration where the integrand switches from ~1 to ~0
+        let val = integrator.integrate(
+            &|theta| {
+                log_derivative_alpha_eq_1(theta, beta) + log_gamma
+            },
+            &|theta| {
+                let log_product = log_derivative_alpha_eq_1(theta, beta) + log_gamma;
+                if log_product.is_nan() { 0.0 } else { (-log_product.exp()).exp() }
+            },
+            -0.5 * PI, 0.5 * PI,
+        )?;
+
+        Ok(val / PI)
+
+    } else {
+
+        // alpha != 1 cases
+        let zeta = -beta * (0.5 * PI * alpha).tan();
+        let eps = (-zeta).atan() / alpha;
+
+        if outside_support(x, zeta, alpha, beta, tol) {
+
+            // Totally skewed with alpha < 1
+            Ok(if beta > 0.0 { 0.0 } else { 1.0 })
+
+        } else if close(x, zeta, tol.zeta) {
+
+            // Special case x = zeta
+            Ok((0.5 * PI - eps) / PI)
+
+        } else if x > zeta {
+
+            // x > zeta
+            let log_gamma = alpha / (alpha - 1.0) * (x - zeta).ln();
+
+            let val = integrator.integrate(
+                &|theta| {
+                    log_derivative_alpha_neq_1(theta, alpha, eps) + log_gamma
+                },
+                &|theta| {
+                    let log_product = log_derivative_alpha_neq_1(theta, alpha, eps) + log_gamma;
+                    if log_product.is_nan() { 0.0 } else { (-log_product.exp()).exp() }
+                },
+                -eps, 0.5 * PI,
+            )?;
+
+            if alpha < 1.0 {
+                Ok((0.5 * PI - eps) / PI + val / PI)
+            } else {
+                Ok(1.0 - val / PI)
+            }
+
+        } else {
+            // symmetric case
+            Ok(1.0 - cdf_scaled(-x, alpha, -beta, tol, integrator)?)
+        }
+    }
+}
+
+// Tests if x lies outside the half-line supporting a totally skewed distribution with alpha < 1.
+fn outside_support(x: f64, zeta: f64, alpha: f64, beta: f64, tol: &Tol) -> bool {
+    alpha < 1.0 && close(beta.abs(), 1.0, tol.beta) && (x - zeta) * beta.signum() < 0.0
 }
 
 // Utility to test closeness
@@ -240,38 +870,66 @@ pub(crate) fn close( arg: f64, close_to: f64, with_tol: f64 ) -> bool {
     (arg-close_to).abs() <= with_tol.abs()
 }
 
-fn derivative_alpha_neq_1(theta: f64, alpha: f64, eps: f64) -> f64 {
-    (alpha * eps).cos().powf(1.0/(alpha  - 1.0)) *
-    (theta.cos() / (alpha * (theta + eps)).sin()).powf(alpha / (alpha - 1.0)) *
-    (alpha * eps + (alpha - 1.0) * theta).cos() / theta.cos()
+// Logarithm of V(theta), formed term by term since V itself overflows for alpha close to 1 and far in the tails.
+fn log_derivative_alpha_neq_1(theta: f64, alpha: f64, eps: f64) -> f64 {
+    (alpha * eps).cos().ln() / (alpha - 1.0) +
+    alpha / (alpha - 1.0) * (theta.cos() / (alpha * (theta + eps)).sin()).ln() +
+    ((alpha * eps + (alpha - 1.0) * theta).cos() / theta.cos()).ln()
 }
 
-fn integrand_alpha_neq_1(theta: f64, alpha: f64, eps: f64, gamma: f64) -> f64 {
-    let val = derivative_alpha_neq_1(theta, alpha, eps);
-    if val.is_nan() || val.is_infinite() {
-        return 0.0;
-    }
-    (val.ln() - val * gamma).exp()
+fn log_derivative_alpha_eq_1(theta: f64, beta: f64) -> f64 {
+    (1.0 + 2.0 * beta * theta / PI).ln() + (0.5 * PI / beta + theta) * theta.tan() - theta.cos().ln()
 }
 
-fn derivative_alpha_eq_1(theta: f64, beta: f64) -> f64 {
-    (1.0 + 2.0 * beta * theta / PI) * ( (0.5 * PI / beta + theta) * theta.tan() ).exp() / theta.cos()
+// Partial derivatives of the logarithm of V(theta) with respect to alpha and eps.
+fn log_derivative_alpha_neq_1_gradient(theta: f64, alpha: f64, eps: f64) -> (f64, f64) {
+    let m = alpha - 1.0;
+    let s = alpha * (theta + eps);
+    let c = alpha * eps + m * theta;
+    let d_alpha = -eps * (alpha * eps).tan() / m - (alpha * eps).cos().ln() / (m * m)
+        - (theta.cos() / s.sin()).ln() / (m * m) - alpha * (theta + eps) / (m * s.tan())
+        - (theta + eps) * c.tan();
+    let d_eps = -alpha * (alpha * eps).tan() / m - alpha * alpha / (m * s.tan()) - alpha * c.tan();
+    (d_alpha, d_eps)
 }
 
-fn integrand_alpha_eq_1(theta: f64, beta: f64, gamma: f64) -> f64 {
-    let val = derivative_alpha_eq_1(theta, beta);
-    if val.is_nan() || val.is_infinite() {
+// gamma V exp(-gamma V), from the logarithm of gamma V.
+fn integrand(log_product: f64) -> f64 {
+    if log_product.is_nan() || log_product.is_infinite() {
         return 0.0;
     }
-    (val.ln() - val * gamma).exp()
+    (log_product - log_product.exp()).exp()
+}
+
+// (1 - gamma V) gamma V exp(-gamma V): the derivative of the integrand with respect to the logarithm of gamma V.
+fn integrand_derivative(log_product: f64) -> f64 {
+    let val = integrand(log_product);
+    if val == 0.0 {
+        return 0.0;
+    }
+    (1.0 - log_product.exp()) * val
+}
+
+// Products with the integrand vanish where its factors overflow at the end points of the range.
+fn finite_or_zero(val: f64) -> f64 {
+    if val.is_finite() { val } else { 0.0 }
+}
+
+/// Parameterisation of a distribution: Standard form S_alpha(sigma, beta, mu) or Nolan's form S^0_alpha(sigma, beta, mu_0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Form {
+    /// S_alpha(sigma, beta, mu), as taken by AlphaStable::new.
+    Standard,
+    /// S^0_alpha(sigma, beta, mu_0), as taken by AlphaStable::new_S0, which is continuous in alpha and beta.
+    Nolan,
 }
 
 /// Defines tolerances for testing if alpha, beta and zeta approach special values.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Tol {
-    alpha: f64,
-    beta: f64,
-    zeta: f64,
+    pub(crate) alpha: f64,
+    pub(crate) beta: f64,
+    pub(crate) zeta: f64,
 }
 
 impl Tol {
@@ -287,3 +945,245 @@ impl Default for Tol {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use gkquad::single::integral;
+    use num_complex::Complex64;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use spfunc::gamma::gamma;
+
+    use super::{close, AlphaStable};
+    use crate::error::Error;
+    use crate::special::erfc;
+
+    #[test]
+    fn test_cdf_normal() {
+        // alpha == 2 is normal with variance 2 sigma^2
+        let dist = AlphaStable::new(2.0, 0.0, 1.0, 0.5).unwrap();
+        assert!(close(dist.cdf(0.5).unwrap(), 0.5, 1e-15));
+        assert!(close(dist.cdf(0.5 + 2.0_f64.sqrt()).unwrap(), 0.8413447460685429, 1e-12));
+    }
+
+    // Values of S_alpha(1, beta, 0) found independently by inverting the characteristic function, as (alpha, beta, x, value)
+    const PDF_VALUES: [(f64, f64, f64, f64); 13] = [
+        (1.5, 0.5, -2.0, 0.133306608096193),
+        (1.5, 0.5, 0.3, 0.2221322622784707),
+        (1.5, 0.5, 4.0, 0.015129501902705522),
+        (0.8, -0.3, -1.0, 0.30962467834820945),
+        (0.8, -0.3, 3.0, 0.013658825635457146),
+        (1.2, 1.0, -1.0, 0.09731757622498088),
+        (1.9, 0.5, 15.0, 5.6166023062456266e-05),
+        (1.0, 0.5, -2.0, 0.040886666216885414),
+        (1.0, 0.1, -8.0, 0.004339714269812465),
+        (1.0, 0.1, -20.0, 0.0007053972019508482),
+        (1.0, -0.1, 8.0, 0.004339714269812465),
+        (1.001, 0.5, 400.0, 9.243113186393505e-07),
+        (1.001, 0.5, -50.0, 6.671549281862141e-06),
+    ];
+    const CDF_VALUES: [(f64, f64, f64, f64); 7] = [
+        (1.5, 0.5, -2.0, 0.11629980196823686),
+        (1.5, 0.5, 0.3, 0.6699805578358116),
+        (0.8, -0.3, 0.5, 0.8487219688830967),
+        (1.0, 0.5, 1.0, 0.663545098251682),
+        (1.0, 0.1, -8.0, 0.03519466221324402),
+        (1.0, 0.1, -20.0, 0.014197812364925066),
+        (1.001, 0.5, 400.0, 0.9993382317045767),
+    ];
+
+    #[test]
+    fn test_pdf_values() {
+        // Including the far tails for alpha == 1 with small beta and for alpha close to 1, where gamma V overflows
+        for (alpha, beta, x, expected) in PDF_VALUES {
+            let dist = AlphaStable::new(alpha, beta, 1.0, 0.0).unwrap();
+            assert!(close(dist.pdf(x).unwrap() / expected, 1.0, 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_cdf_values() {
+        for (alpha, beta, x, expected) in CDF_VALUES {
+            let dist = AlphaStable::new(alpha, beta, 1.0, 0.0).unwrap();
+            assert!(close(dist.cdf(x).unwrap() / expected, 1.0, 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_cdf_matches_integrated_pdf() {
+        for (alpha, beta) in [(0.7, 0.3), (1.0, 0.5), (1.0, -0.5), (1.5, 0.5), (1.5, -1.0)] {
+            let dist = AlphaStable::new(alpha, beta, 1.5, 0.2).unwrap();
+            let (a, b) = (-1.0, 2.0);
+            let mass = integral(|x: f64| dist.pdf(x).unwrap(), a..b).estimate().unwrap();
+            let diff = dist.cdf(b).unwrap() - dist.cdf(a).unwrap();
+            assert!(close(diff, mass, 1e-7));
+        }
+    }
+
+    #[test]
+    fn test_levy_closed_form() {
+        let (c, mu) = (2.0, 1.0);
+        let dist = AlphaStable::new(0.5, 1.0, c, mu).unwrap();
+        for x in [1.5, 2.0, 5.0, 40.0] {
+            let pdf = (c / (2.0 * PI)).sqrt() * (-c / (2.0 * (x - mu))).exp() / (x - mu).powf(1.5);
+            assert!(close(dist.pdf(x).unwrap(), pdf, 1e-14));
+            assert!(close(dist.cdf(x).unwrap(), erfc((c / (2.0 * (x - mu))).sqrt()), 1e-14));
+        }
+
+        // Quadrature just outside the tolerance agrees with the closed form
+        let near = AlphaStable::new(0.50001, 1.0, c, mu).unwrap();
+        assert!(close(near.pdf(3.0).unwrap(), dist.pdf(3.0).unwrap(), 1e-4));
+
+        // Mirror image for beta == -1
+        let mirror = AlphaStable::new(0.5, -1.0, c, -mu).unwrap();
+        assert!(close(mirror.pdf(-3.0).unwrap(), dist.pdf(3.0).unwrap(), 1e-14));
+        assert!(close(mirror.cdf(-3.0).unwrap(), 1.0 - dist.cdf(3.0).unwrap(), 1e-14));
+    }
+
+    #[test]
+    fn test_pdf_derivative() {
+        let h = 1e-5;
+        for (alpha, beta) in [(1.5, 0.5), (0.7, -0.3), (1.0, 0.5), (1.0, 0.0), (2.0, 0.0), (0.5, 1.0)] {
+            let dist = AlphaStable::new(alpha, beta, 1.5, 0.2).unwrap();
+            for x in [-2.0, -0.3, 0.4, 3.0] {
+                let difference = (dist.pdf(x + h).unwrap() - dist.pdf(x - h).unwrap()) / (2.0 * h);
+                assert!(close(dist.pdf_derivative(x).unwrap(), difference, 1e-7));
+            }
+        }
+    }
+
+    #[test]
+    fn test_score() {
+        let h = 1e-5;
+        for (alpha, beta, sigma, mu) in [(1.3, 0.4, 2.0, 1.0), (0.8, -0.6, 0.5, -1.0), (1.0, 0.5, 1.5, 0.0)] {
+            let dist = AlphaStable::new(alpha, beta, sigma, mu).unwrap();
+            for x in [-1.5, 0.5, 4.0] {
+                let score = dist.score(x).unwrap();
+                for (i, s) in score.iter().enumerate() {
+                    let log_pdf = |t: f64| {
+                        let mut params = [alpha, beta, sigma, mu];
+                        params[i] += t;
+                        AlphaStable::new(params[0], params[1], params[2], params[3]).unwrap().pdf(x).unwrap().ln()
+                    };
+                    // Standard form is discontinuous in alpha at alpha == 1
+                    if i == 0 && alpha == 1.0 {
+                        continue;
+                    }
+                    assert!(close(*s, (log_pdf(h) - log_pdf(-h)) / (2.0 * h), 1e-5));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_expected_shortfall() {
+        // Compare with -(1 / level) times the integral of x f(x) below the quantile at level, with the integral below
+        // q - 10^4 from the asymptotic density alpha c |x|^(-alpha - 1), c = sigma^alpha (1 - beta) Gamma(alpha) sin(pi alpha / 2) / pi
+        for (alpha, beta) in [(1.5, 0.0), (1.5, 0.5), (1.5, -0.7), (1.8, 0.3), (2.0, 0.0)] {
+            let dist = AlphaStable::new(alpha, beta, 2.0, 0.5).unwrap();
+            for level in [0.01, 0.05, 0.5, 0.8] {
+                let q = dist.quantile(level).unwrap();
+                assert!(close(dist.value_at_risk(level).unwrap(), -q, 1e-12));
+                let mut direct = 0.0;
+                let mut upper = q;
+                for lower in [q - 10.0, q - 100.0, q - 1000.0, q - 10000.0] {
+                    direct += integral(|x: f64| x * dist.pdf(x).unwrap(), lower..upper).estimate().unwrap();
+                    upper = lower;
+                }
+                let c = 2.0_f64.powf(alpha) * (1.0 - beta) * gamma(alpha) * (0.5 * PI * alpha).sin() / PI;
+                direct -= alpha * c * (-upper).powf(1.0 - alpha) / (alpha - 1.0);
+                let direct = -direct / level;
+                let es = dist.expected_shortfall(level).unwrap();
+                assert!(close(es, direct, 1e-5 * direct.abs().max(1.0)));
+            }
+        }
+        let dist = AlphaStable::new(1.0, 0.5, 1.0, 0.0).unwrap();
+        assert!(matches!(dist.expected_shortfall(0.05), Err(Error::InfiniteExpectedShortfallError { .. })));
+        assert!(matches!(dist.expected_shortfall(0.0), Err(Error::ProbabilityError { .. })));
+    }
+
+    #[test]
+    fn test_algebra() {
+        // Characteristic functions multiply under convolution, and phi_{cX + d}(t) = exp(itd) phi_X(ct)
+        for alpha in [0.7, 1.0, 1.5] {
+            let x = AlphaStable::new_S0(alpha, 0.6, 1.5, -0.3).unwrap();
+            let y = AlphaStable::new(alpha, -0.2, 0.5, 2.0).unwrap();
+            let sum = (&x + &y).unwrap();
+            let scaled = (&x * -2.5).unwrap();
+            let shifted = x.shift(0.7);
+            let negated = x.negate();
+            for t in [-1.3, 0.4, 2.0_f64] {
+                assert!((sum.characteristic_function(t) - x.characteristic_function(t) * y.characteristic_function(t)).norm() < 1e-14);
+                assert!((scaled.characteristic_function(t) - x.characteristic_function(-2.5 * t)).norm() < 1e-14);
+                let phase = Complex64::from_polar(1.0, 0.7 * t);
+                assert!((shifted.characteristic_function(t) - phase * x.characteristic_function(t)).norm() < 1e-14);
+                assert!((negated.characteristic_function(t) - x.characteristic_function(-t)).norm() < 1e-14);
+            }
+        }
+
+        let x = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        let y = AlphaStable::new(1.2, 0.0, 1.0, 0.0).unwrap();
+        assert!(matches!(x.clone() + y, Err(Error::AlphaMismatchError { alpha_1, alpha_2 }) if alpha_1 == 1.5 && alpha_2 == 1.2));
+        assert!(matches!(x * 0.0, Err(Error::ScaleError { .. })));
+    }
+
+    #[test]
+    fn test_characteristic_function() {
+        // Standard form: ln phi(t) = -sigma^alpha |t|^alpha (1 - i beta sign(t) tan(pi alpha / 2)) + i mu t, or with
+        // tan(pi alpha / 2) replaced by -(2 / pi) ln|t| when alpha == 1
+        let (beta, sigma, mu) = (0.5, 2.0, 1.0);
+        for alpha in [1.5, 1.0, 0.6] {
+            let dist = AlphaStable::new(alpha, beta, sigma, mu).unwrap();
+            assert_eq!(dist.characteristic_function(0.0), Complex64::new(1.0, 0.0));
+            for t in [-1.3, 0.4, 2.0_f64] {
+                let skew = if alpha == 1.0 { -2.0 * t.abs().ln() / PI } else { (0.5 * PI * alpha).tan() };
+                let log_phi = Complex64::new(-(sigma * t.abs()).powf(alpha), (sigma * t.abs()).powf(alpha) * beta * t.signum() * skew + mu * t);
+                assert!((dist.characteristic_function(t) - log_phi.exp()).norm() < 1e-14);
+            }
+        }
+    }
+
+    #[test]
+    fn test_one_sided_support() {
+        let dist = AlphaStable::new(0.7, 1.0, 1.0, 2.0).unwrap();
+        assert_eq!(dist.support(), (2.0, f64::INFINITY));
+        assert_eq!(dist.pdf(1.9).unwrap(), 0.0);
+        assert_eq!(dist.cdf(1.9).unwrap(), 0.0);
+        assert!(dist.pdf(2.5).unwrap() > 0.0);
+
+        let dist = AlphaStable::new(0.7, -1.0, 1.0, 2.0).unwrap();
+        assert_eq!(dist.support(), (f64::NEG_INFINITY, 2.0));
+        assert_eq!(dist.pdf(2.1).unwrap(), 0.0);
+        assert_eq!(dist.cdf(2.1).unwrap(), 1.0);
+
+        let dist = AlphaStable::new(1.5, 1.0, 1.0, 2.0).unwrap();
+        assert_eq!(dist.support(), (f64::NEG_INFINITY, f64::INFINITY));
+    }
+
+    #[test]
+    fn test_positive_sampler_laplace_transform() {
+        // E[exp(-X)] = exp(-sigma^alpha / cos(pi alpha / 2)) for X ~ S_alpha(sigma, 1, 0) with alpha < 1
+        let mut rng = StdRng::seed_from_u64(1);
+        let (alpha, sigma) = (0.6, 0.8);
+        let dist = AlphaStable::new(alpha, 1.0, sigma, 0.0).unwrap();
+        let n = 50000;
+        let samples: Vec<f64> = (0..n).map(|_| dist.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|x| *x >= 0.0));
+        let laplace = samples.iter().map(|x| (-x).exp()).sum::<f64>() / n as f64;
+        let expected = (-sigma.powf(alpha) / (0.5 * PI * alpha).cos()).exp();
+        assert!(close(laplace, expected, 0.01));
+    }
+
+    #[test]
+    fn test_quantile() {
+        let dist = AlphaStable::new(1.2, 0.4, 2.0, 1.0).unwrap();
+        for p in [1e-4, 0.1, 0.5, 0.9, 0.999] {
+            let x = dist.quantile(p).unwrap();
+            assert!(close(dist.cdf(x).unwrap(), p, 1e-9));
+        }
+        assert!(matches!(dist.quantile(0.0), Err(Error::ProbabilityError { .. })));
+        assert!(matches!(dist.quantile(1.0), Err(Error::ProbabilityError { .. })));
+    }
+}
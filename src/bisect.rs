@@ -35,7 +35,7 @@ pub(crate) fn bisect(f: &dyn Fn(f64) -> f64, mut a: f64, mut b:f64, eps: f64, n_
             b = x;
         }
     }
-    return NumericalResult::new(x,0.5*(b-a), Some(Error::BisectionIterationsExceededError { n_max }));    
+    NumericalResult::new(x,0.5*(b-a), Some(Error::BisectionIterationsExceededError { n_max }))
 }
 
 #[cfg(test)]
@@ -51,7 +51,7 @@ mod tests {
             Ok(estimate) => {
                 assert!(close(estimate, 3.0 , 1e-6));
             },
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
         assert!(!result.has_err());
     }
@@ -64,7 +64,7 @@ mod tests {
             Ok(estimate) => {
                 assert!(close(estimate, 6.0_f64.sqrt() , 1e-6));
             },
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
         assert!(!result.has_err());
     }
@@ -0,0 +1,207 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+use rand_distr::{Distribution, Poisson};
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+
+/// Defines Steutel and van Harn's discrete stable distribution on the non-negative integers, with probability generating
+/// function G(z) = E[z^N] = exp(-lambda (1 - z)^alpha), 0 < alpha <= 1 and lambda > 0.
+///
+/// N is Poisson distributed with a random mean L, whose Laplace transform is E[exp(-sL)] = exp(-lambda s^alpha): for
+/// alpha < 1 the positive alpha stable distribution S_alpha((lambda cos(pi alpha / 2))^(1/alpha), 1, 0) in Standard form,
+/// and for alpha == 1 the constant lambda, when N is Poisson distributed with mean lambda. For alpha < 1,
+/// P(N = n) ~ lambda alpha Gamma(n - alpha) / (Gamma(1 - alpha) n!) ~ n^(-1-alpha) and the mean is infinite.
+#[derive(Debug, Clone)]
+pub struct DiscreteStable {
+    alpha: f64,
+    lambda: f64,
+    mixing: Option<AlphaStable>,
+}
+
+impl DiscreteStable {
+
+    /// Create distribution from alpha in (0, 1] and lambda > 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::DiscreteStable;
+    ///
+    /// let distribution = DiscreteStable::new(0.5, 2.0).unwrap();
+    /// let val = distribution.pmf(3);
+    /// ```
+    pub fn new(alpha: f64, lambda: f64) -> Result<DiscreteStable, Error> {
+
+        if alpha <= 0.0 || alpha > 1.0 || alpha.is_nan() {
+            return Err(Error::UnitAlphaError { alpha });
+        }
+
+        if lambda <= 0.0 || !lambda.is_finite() {
+            return Err(Error::ScaleError { scale: lambda });
+        }
+
+        let mixing = if alpha < 1.0 {
+            Some(AlphaStable::new(alpha, 1.0, (lambda * (0.5 * PI * alpha).cos()).powf(1.0 / alpha), 0.0)?)
+        } else {
+            None
+        };
+
+        Ok(DiscreteStable { alpha, lambda, mixing })
+    }
+
+    /// Return parameters as tuple of (alpha, lambda).
+    pub fn get_params(&self) -> (f64, f64) {
+        (self.alpha, self.lambda)
+    }
+
+    /// Return the positive alpha stable distribution of the Poisson mean, or None when alpha == 1 and the mean is lambda.
+    pub fn get_mixing_distribution(&self) -> Option<&AlphaStable> {
+        self.mixing.as_ref()
+    }
+
+    /// Probability generating function E[z^N] for z in [0, 1].
+    pub fn pgf(&self, z: f64) -> f64 {
+        (-self.lambda * (1.0 - z).powf(self.alpha)).exp()
+    }
+
+    /// Probability mass function at n.
+    ///
+    /// This runs the O(n^2) recursion of [`pmfs`](Self::pmfs) up to n, so to evaluate many points call `pmfs` once.
+    pub fn pmf(&self, n: usize) -> f64 {
+        self.pmfs(n)[n]
+    }
+
+    /// Probability mass function at 0, 1, ..., n_max.
+    ///
+    /// The coefficients of the power series of G(z) = exp(h(z)), h(z) = -lambda (1 - z)^alpha, follow from G' = h' G as
+    /// P(N = n) = (1 / n) sum_{k=1..n} k h_k P(N = n - k), where k h_k = lambda alpha prod_{j=1..k-1} (j - alpha) / j
+    /// is positive, so that the sum is free of cancellation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::DiscreteStable;
+    ///
+    /// let pmfs = DiscreteStable::new(0.5, 2.0).unwrap().pmfs(10);
+    /// assert_eq!(pmfs.len(), 11);
+    /// assert_eq!(pmfs[0], (-2.0_f64).exp());
+    /// ```
+    pub fn pmfs(&self, n_max: usize) -> Vec<f64> {
+
+        let mut coefficients = Vec::with_capacity(n_max);
+        let mut c = self.lambda * self.alpha;
+        for k in 1..=n_max {
+            coefficients.push(c);
+            c *= (k as f64 - self.alpha) / k as f64;
+        }
+
+        let mut p = Vec::with_capacity(n_max + 1);
+        p.push((-self.lambda).exp());
+        for n in 1..=n_max {
+            let val = coefficients[..n].iter().zip(p.iter().rev()).map(|(c, p)| c * p).sum::<f64>();
+            p.push(val / n as f64);
+        }
+        p
+    }
+
+    /// Cumulative distribution function P(N <= n).
+    ///
+    /// Like [`pmf`](Self::pmf), this runs the O(n^2) recursion of [`pmfs`](Self::pmfs) up to n.
+    pub fn cdf(&self, n: usize) -> f64 {
+        self.pmfs(n).iter().sum::<f64>().min(1.0)
+    }
+
+    /// Sample from the distribution as a Poisson variable whose mean is drawn from the mixing distribution.
+    ///
+    /// For small alpha the mixing variable can exceed the range of the Poisson sampler or overflow to infinity, and the
+    /// sample then saturates at u64::MAX.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::DiscreteStable;
+    ///
+    /// let mut rng = thread_rng();
+    /// let distribution = DiscreteStable::new(0.7, 1.0).unwrap();
+    /// let n = distribution.sample(&mut rng);
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let mean = match &self.mixing {
+            Some(mixing) => mixing.sample(rng),
+            None => self.lambda,
+        };
+        match Poisson::new(mean) {
+            Ok(poisson) => poisson.sample(rng) as u64,
+            // The mixing variable is positive, but may round to zero
+            Err(_) if mean <= 0.0 => 0,
+            // An infinite mean saturates
+            Err(_) => u64::MAX,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::DiscreteStable;
+    use crate::alpha_stable::close;
+    use crate::error::Error;
+
+    #[test]
+    fn test_poisson() {
+        // alpha == 1 gives the Poisson distribution
+        let dist = DiscreteStable::new(1.0, 3.0).unwrap();
+        let mut poisson = (-3.0_f64).exp();
+        for (n, p) in dist.pmfs(20).iter().enumerate() {
+            assert!(close(*p, poisson, 1e-15));
+            poisson *= 3.0 / (n + 1) as f64;
+        }
+        assert!(dist.get_mixing_distribution().is_none());
+    }
+
+    #[test]
+    fn test_pmf() {
+        // Probabilities reproduce the probability generating function, and have the power law tail
+        let dist = DiscreteStable::new(0.6, 1.5).unwrap();
+        let pmfs = dist.pmfs(2000);
+        for z in [0.0, 0.3, 0.7] {
+            let series = pmfs.iter().rev().fold(0.0, |acc, p| acc * z + p);
+            assert!(close(series, dist.pgf(z), 1e-14));
+        }
+        assert!(close(dist.pmf(5), pmfs[5], 1e-16));
+        assert!(close(dist.cdf(2000), pmfs.iter().sum(), 1e-15));
+
+        // P(N = n) ~ lambda alpha Gamma(n - alpha) / (Gamma(1 - alpha) n!) ~ lambda alpha n^(-1-alpha) / Gamma(1 - alpha)
+        let gamma = spfunc::gamma::gamma(0.4);
+        let tail = 1.5 * 0.6 * 2000.0_f64.powf(-1.6) / gamma;
+        assert!(close(pmfs[2000] / tail, 1.0, 0.05));
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(21);
+        let dist = DiscreteStable::new(0.6, 1.5).unwrap();
+        let n = 20000;
+        let mut counts = [0; 5];
+        for _ in 0..n {
+            let k = dist.sample(&mut rng) as usize;
+            if k < 5 {
+                counts[k] += 1;
+            }
+        }
+        for (count, p) in counts.iter().zip(dist.pmfs(4)) {
+            assert!(close(*count as f64 / n as f64, p, 0.01));
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(matches!(DiscreteStable::new(1.5, 1.0), Err(Error::UnitAlphaError { .. })));
+        assert!(matches!(DiscreteStable::new(0.0, 1.0), Err(Error::UnitAlphaError { .. })));
+        assert!(matches!(DiscreteStable::new(0.5, 0.0), Err(Error::ScaleError { .. })));
+    }
+}
@@ -10,6 +10,78 @@ pub enum Error {
     #[error("beta ({}) outside allowed range [-1,1]", beta)]
     BetaError { beta: f64},
 
+    /// Raised when distributions that must share alpha do not
+    #[error("alpha ({}) does not match alpha ({})", alpha_1, alpha_2)]
+    AlphaMismatchError { alpha_1: f64, alpha_2: f64},
+
+    /// Raised when a probability is outside allowed range (0,1)
+    #[error("probability ({}) outside allowed range (0,1)", p)]
+    ProbabilityError { p: f64},
+
+    /// Raised when Expected Shortfall is requested for alpha <= 1, for which the mean, and so Expected Shortfall, is infinite
+    #[error("expected shortfall is infinite for alpha ({}) <= 1", alpha)]
+    InfiniteExpectedShortfallError { alpha: f64},
+
+    /// Raised when truncation bounds do not satisfy lower < upper
+    #[error("truncation bounds ({},{}) must satisfy lower < upper", lower, upper)]
+    TruncationBoundsError { lower: f64, upper: f64},
+
+    /// Raised when the truncation interval carries negligible probability mass
+    #[error("truncation interval has negligible probability mass ({})", mass)]
+    NegligibleMassError { mass: f64},
+
+    /// Raised when a time grid is empty or not strictly increasing
+    #[error("time grid is empty or not strictly increasing at index {}", index)]
+    TimeGridError { index: usize },
+
+    /// Raised when a grid of arguments of a characteristic function is empty, not positive or not strictly increasing
+    #[error("argument grid is empty, not positive or not strictly increasing at index {}", index)]
+    ArgumentGridError { index: usize },
+
+    /// Raised when inputs that must have equal lengths do not
+    #[error("length mismatch: expected {}, found {}", expected, found)]
+    LengthMismatchError { expected: usize, found: usize },
+
+    /// Raised when a weight is negative or not finite, or weights sum to zero
+    #[error("weight ({}) must be non-negative and finite, and weights must not all be zero", weight)]
+    WeightError { weight: f64},
+
+    /// Raised when a Hurst index is outside allowed range (0,1)
+    #[error("Hurst index ({}) outside allowed range (0,1)", h)]
+    HurstError { h: f64},
+
+    /// Raised when a mean reversion rate is not positive
+    #[error("mean reversion rate ({}) must be positive", lambda)]
+    MeanReversionError { lambda: f64},
+
+    /// Raised when there are too few observations to fit a model
+    #[error("sample size ({}) too small", n)]
+    SampleSizeError { n: usize },
+
+    /// Raised when a GARCH omega is not positive or a GARCH coefficient is negative
+    #[error("GARCH parameter ({}) must be non-negative, and omega positive", value)]
+    GarchParameterError { value: f64},
+
+    /// Raised when a scale parameter is not positive
+    #[error("scale ({}) must be positive", scale)]
+    ScaleError { scale: f64},
+
+    /// Raised when a dimension is zero
+    #[error("dimension ({}) must be at least one", dimension)]
+    DimensionError { dimension: usize },
+
+    /// Raised when the number of order statistics used by a tail index estimator is not valid for the sample
+    #[error("number of order statistics ({}) not valid for sample size ({})", k, n)]
+    OrderStatisticError { k: usize, n: usize },
+
+    /// Raised when the posterior density vanishes at the point from which sampling starts
+    #[error("posterior density vanishes at the starting point")]
+    PosteriorSupportError,
+
+    /// Raised when a matrix that must be inverted is singular
+    #[error("matrix is singular")]
+    SingularMatrixError,
+
     /// Raised by pdf function when initial values of bisection do not bracket a root
     #[error("bisection range ({},{}) does not bracket a root", a, b)]
     BisectionRangeError { a: f64, b: f64},
@@ -18,6 +90,34 @@ pub enum Error {
     #[error("root not found. Exceeded iteration limit of {}", n_max)]
     BisectionIterationsExceededError { n_max: u64 },
 
+    /// Raised when alpha of a tempered stable distribution is outside allowed range (0,1) or (1,2)
+    #[error("alpha ({}) outside allowed range (0,1) or (1,2)", alpha)]
+    TemperedAlphaError { alpha: f64},
+
+    /// Raised when alpha of a discrete stable or Mittag-Leffler distribution is outside allowed range (0,1]
+    #[error("alpha ({}) outside allowed range (0,1]", alpha)]
+    UnitAlphaError { alpha: f64},
+
+    /// Raised when tail constants are negative or both zero
+    #[error("tail constants ({},{}) must be non-negative and not both zero", c_plus, c_minus)]
+    TailConstantError { c_plus: f64, c_minus: f64},
+
+    /// Raised when a tempering parameter is not positive
+    #[error("tempering parameter ({}) must be positive", lambda)]
+    TemperingError { lambda: f64},
+
+    /// Raised when no exact sampler is available for the requested alpha
+    #[error("no exact sampler available for alpha ({})", alpha)]
+    ExactSamplerError { alpha: f64},
+
+    /// Raised when the number of points in an FFT grid is not a power of two
+    #[error("grid size ({}) must be a power of two", n)]
+    GridSizeError { n: usize },
+
+    /// Raised when every particle of a particle filter has zero likelihood
+    #[error("all particle weights vanished at time ({})", t)]
+    ParticleDegeneracyError { t: usize },
+
     /// Raised by GkQuad library used in pdf function
     #[error("integration error")]
     GkQuad {
@@ -0,0 +1,179 @@
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+
+// In-place radix-2 Cooley-Tukey FFT. The length of buf must be a power of two.
+// The inverse transform is not scaled by 1/n.
+pub(crate) fn fft(buf: &mut [Complex64], inverse: bool) {
+
+    let n = buf.len();
+    if n <= 1 {
+        return;
+    }
+    debug_assert!(n.is_power_of_two());
+
+    // Bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            buf.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let w_len = Complex64::from_polar(1.0, sign * 2.0 * PI / len as f64);
+        for start in (0..n).step_by(len) {
+            let mut w = Complex64::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let u = buf[start + k];
+                let v = buf[start + k + len / 2] * w;
+                buf[start + k] = u + v;
+                buf[start + k + len / 2] = u - v;
+                w *= w_len;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+// Linear convolution of two real sequences using zero padded FFTs.
+pub(crate) fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+
+    let mut fa: Vec<Complex64> = a.iter().map(|&x| Complex64::new(x, 0.0)).collect();
+    let mut fb: Vec<Complex64> = b.iter().map(|&x| Complex64::new(x, 0.0)).collect();
+    fa.resize(n, Complex64::new(0.0, 0.0));
+    fb.resize(n, Complex64::new(0.0, 0.0));
+
+    fft(&mut fa, false);
+    fft(&mut fb, false);
+    for (x, y) in fa.iter_mut().zip(fb.iter()) {
+        *x *= y;
+    }
+    fft(&mut fa, true);
+
+    fa.iter().take(len).map(|z| z.re / n as f64).collect()
+}
+
+// Linear convolution of two real arrays, stored as rows, using zero padded two dimensional FFTs.
+pub(crate) fn convolve_2d(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+
+    if a.is_empty() || b.is_empty() || a[0].is_empty() || b[0].is_empty() {
+        return Vec::new();
+    }
+
+    let (rows, cols) = (a.len() + b.len() - 1, a[0].len() + b[0].len() - 1);
+    let (n_rows, n_cols) = (rows.next_power_of_two(), cols.next_power_of_two());
+
+    let transform = |x: &[Vec<f64>]| {
+        let mut buf = vec![Complex64::new(0.0, 0.0); n_rows * n_cols];
+        for (i, row) in x.iter().enumerate() {
+            for (j, x) in row.iter().enumerate() {
+                buf[i * n_cols + j] = Complex64::new(*x, 0.0);
+            }
+        }
+        fft_2d(&mut buf, n_rows, n_cols, false);
+        buf
+    };
+    let mut fa = transform(a);
+    let fb = transform(b);
+    for (x, y) in fa.iter_mut().zip(fb.iter()) {
+        *x *= y;
+    }
+    fft_2d(&mut fa, n_rows, n_cols, true);
+
+    let n = (n_rows * n_cols) as f64;
+    (0..rows).map(|i| (0..cols).map(|j| fa[i * n_cols + j].re / n).collect()).collect()
+}
+
+// In-place FFT of an n_rows by n_cols array stored by rows: transforms of the rows, then of the columns.
+fn fft_2d(buf: &mut [Complex64], n_rows: usize, n_cols: usize, inverse: bool) {
+    for row in buf.chunks_mut(n_cols) {
+        fft(row, inverse);
+    }
+    let mut column = vec![Complex64::new(0.0, 0.0); n_rows];
+    for j in 0..n_cols {
+        for (i, c) in column.iter_mut().enumerate() {
+            *c = buf[i * n_cols + j];
+        }
+        fft(&mut column, inverse);
+        for (i, c) in column.iter().enumerate() {
+            buf[i * n_cols + j] = *c;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex64;
+
+    use super::{convolve, convolve_2d, fft};
+    use crate::alpha_stable::close;
+
+    #[test]
+    fn test_inverse_round_trip() {
+        let x: Vec<Complex64> = (0..16).map(|k| Complex64::new((k as f64).sin(), 0.5 * k as f64)).collect();
+        let mut buf = x.clone();
+        fft(&mut buf, false);
+        fft(&mut buf, true);
+        for (y, z) in buf.iter().zip(x.iter()) {
+            assert!(close(y.re / 16.0, z.re, 1e-12) && close(y.im / 16.0, z.im, 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_impulse() {
+        let mut buf = vec![Complex64::new(0.0, 0.0); 8];
+        buf[1] = Complex64::new(1.0, 0.0);
+        fft(&mut buf, false);
+        for (k, y) in buf.iter().enumerate() {
+            let expected = Complex64::from_polar(1.0, -2.0 * std::f64::consts::PI * k as f64 / 8.0);
+            assert!(close(y.re, expected.re, 1e-12) && close(y.im, expected.im, 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_convolve() {
+        let c = convolve(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5]);
+        let expected = [0.0, 1.0, 2.5, 4.0, 1.5];
+        assert_eq!(c.len(), expected.len());
+        for (x, y) in c.iter().zip(expected.iter()) {
+            assert!(close(*x, *y, 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_convolve_2d() {
+        let a = vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![0.5, -1.0]];
+        let b = vec![vec![0.0, 1.0, 2.0], vec![-1.0, 0.5, 0.0]];
+        let c = convolve_2d(&a, &b);
+        assert_eq!((c.len(), c[0].len()), (4, 4));
+        for (i, row) in c.iter().enumerate() {
+            for (j, x) in row.iter().enumerate() {
+                let mut expected = 0.0;
+                for (k, a_row) in a.iter().enumerate() {
+                    for (l, a) in a_row.iter().enumerate() {
+                        if i >= k && j >= l && i - k < b.len() && j - l < b[0].len() {
+                            expected += a * b[i - k][j - l];
+                        }
+                    }
+                }
+                assert!(close(*x, expected, 1e-12));
+            }
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::{Mutex, OnceLock};
+
+use crate::alpha_stable::{close, AlphaStable, Form};
+use crate::error::Error;
+use crate::integrator::Integrator;
+use crate::linalg::invert;
+
+impl AlphaStable {
+
+    /// Fisher information matrix of one observation with respect to (alpha, beta, sigma, mu) in Standard form or
+    /// (alpha, beta, sigma, mu_0) in Nolan's form.
+    ///
+    /// For fixed alpha and beta Nolan's form is a location-scale family, so the matrix follows from the information of
+    /// S^0_alpha(1, beta, 0). That is found by integrating products of scores against the pdf, and is cached on a grid of
+    /// (alpha, beta) with spacing 0.05 and interpolated bilinearly between nodes. Standard form follows by the chain rule
+    /// through mu_0 = mu + beta sigma tan(pi alpha / 2), or mu_0 = mu + 2 beta sigma ln(sigma) / pi when alpha == 1.
+    ///
+    /// The information of alpha grows without bound as alpha approaches 2, so for alpha within one grid spacing of 2 it is
+    /// integrated directly rather than interpolated. When alpha == 2 the distribution is normal with variance 2 sigma^2:
+    /// beta has no effect, and alpha lies on the boundary of the parameter space where the information is not defined, so
+    /// in Nolan's form their rows and columns are zero, leaving 2 / sigma^2 for sigma and 1 / (2 sigma^2) for mu_0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::{AlphaStable, Form};
+    ///
+    /// let distribution = AlphaStable::new(1.0, 0.0, 1.0, 0.0).unwrap();
+    /// let information = distribution.fisher_information(Form::Nolan).unwrap();
+    /// assert!((information[3][3] - 0.5).abs() < 1e-6);
+    /// ```
+    pub fn fisher_information(&self, form: Form) -> Result<[[f64; 4]; 4], Error> {
+
+        let (alpha, beta, sigma, _, _) = self.get_params();
+        let standard = if close( alpha, 2.0, self.get_tol().alpha ) {
+            normal_information()
+        } else {
+            interpolated_information(alpha, beta)?
+        };
+
+        // Scores with respect to sigma and mu_0 scale as 1/sigma
+        let d = [1.0, 1.0, 1.0 / sigma, 1.0 / sigma];
+        let mut information = [[0.0; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                information[i][j] = d[i] * d[j] * standard[i][j];
+            }
+        }
+
+        if form == Form::Nolan {
+            return Ok(information);
+        }
+
+        // Derivatives of mu_0 with respect to the parameters of Standard form
+        let gradient = self.mu_0_gradient();
+        let mut jacobian = [[0.0; 4]; 4];
+        for (i, row) in jacobian.iter_mut().enumerate().take(3) {
+            row[i] = 1.0;
+        }
+        jacobian[3] = gradient;
+
+        let mut standard_form = [[0.0; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                standard_form[i][j] = (0..4).flat_map(|k| (0..4).map(move |l| (k, l)))
+                    .map(|(k, l)| jacobian[k][i] * information[k][l] * jacobian[l][j])
+                    .sum();
+            }
+        }
+        Ok(standard_form)
+    }
+
+    /// Asymptotic standard errors of maximum likelihood estimates from n observations: the square roots of the diagonal
+    /// of the inverse Fisher information divided by n.
+    ///
+    /// When alpha == 2 the errors of alpha and beta are not defined and are returned as NaN, and those of sigma and mu are
+    /// the errors of the normal distribution with alpha and beta known, sigma / sqrt(2 n) and sigma sqrt(2 / n).
+    pub fn standard_errors(&self, form: Form, n: usize) -> Result<[f64; 4], Error> {
+        if n == 0 {
+            return Err(Error::SampleSizeError { n });
+        }
+        let information = self.fisher_information(form)?;
+        let mut errors = [0.0; 4];
+        if close( self.get_params().0, 2.0, self.get_tol().alpha ) {
+            let inverse = invert(&[[information[2][2], information[2][3]], [information[3][2], information[3][3]]])
+                .ok_or(Error::SingularMatrixError)?;
+            errors = [f64::NAN, f64::NAN, inverse[0][0], inverse[1][1]];
+        } else {
+            let inverse = invert(&information).ok_or(Error::SingularMatrixError)?;
+            for (i, e) in errors.iter_mut().enumerate() {
+                *e = inverse[i][i];
+            }
+        }
+        Ok(errors.map(|e| (e / n as f64).sqrt()))
+    }
+}
+
+type Information = [[f64; 4]; 4];
+
+// Spacing of the (alpha, beta) grid on which information is cached.
+const FISHER_GRID: f64 = 0.05;
+
+// Half width of the central region integrated with panels of equal width.
+const CENTRAL_WIDTH: f64 = 10.0;
+
+// Number of panels in the central region and in each tail.
+const CENTRAL_PANELS: usize = 40;
+const TAIL_PANELS: usize = 10;
+
+// Information of S^0_alpha(1, beta, 0) interpolated between cached grid nodes. Below the first node, and above the last
+// node before 2 where the information of alpha diverges, it is integrated directly.
+fn interpolated_information(alpha: f64, beta: f64) -> Result<[[f64; 4]; 4], Error> {
+
+    if !(FISHER_GRID..=2.0 - FISHER_GRID).contains(&alpha) {
+        return standard_information(alpha, beta);
+    }
+
+    let snap = |v: f64| if (v - v.round()).abs() < 1e-9 { v.round() } else { v };
+    let (a, b) = (snap(alpha / FISHER_GRID), snap((beta + 1.0) / FISHER_GRID));
+    let (i, j) = (a.floor() as i64, b.floor() as i64);
+    let (s, t) = (a - i as f64, b - j as f64);
+
+    let mut information = [[0.0; 4]; 4];
+    for (di, dj, w) in [(0, 0, (1.0 - s) * (1.0 - t)), (1, 0, s * (1.0 - t)), (0, 1, (1.0 - s) * t), (1, 1, s * t)] {
+        if w == 0.0 {
+            continue;
+        }
+        let node = cached_information(i + di, j + dj)?;
+        for (row, node_row) in information.iter_mut().zip(node.iter()) {
+            for (x, y) in row.iter_mut().zip(node_row.iter()) {
+                *x += w * y;
+            }
+        }
+    }
+    Ok(information)
+}
+
+fn cached_information(i: i64, j: i64) -> Result<[[f64; 4]; 4], Error> {
+
+    static CACHE: OnceLock<Mutex<HashMap<(i64, i64), Information>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+
+    if let Some(information) = cache.lock().unwrap().get(&(i, j)) {
+        return Ok(*information);
+    }
+    let alpha = (i as f64 * FISHER_GRID).min(2.0);
+    let beta = (j as f64 * FISHER_GRID - 1.0).clamp(-1.0, 1.0);
+    let information = standard_information(alpha, beta)?;
+    cache.lock().unwrap().insert((i, j), information);
+    Ok(information)
+}
+
+// Information of S^0_2(1, beta, 0), the normal distribution with variance 2, with zero rows and columns for alpha and beta.
+fn normal_information() -> Information {
+    let mut information = [[0.0; 4]; 4];
+    information[2][2] = 2.0;
+    information[3][3] = 0.5;
+    information
+}
+
+// Information of S^0_alpha(1, beta, 0) with respect to (alpha, beta, sigma, mu_0).
+fn standard_information(alpha: f64, beta: f64) -> Result<[[f64; 4]; 4], Error> {
+    let mut information = [[0.0; 4]; 4];
+    for (x, w) in quadrature_nodes(alpha, beta)? {
+        if let Some((f, s)) = scores(x, alpha, beta)? {
+            for i in 0..4 {
+                for j in 0..4 {
+                    // Far in the tails the pdf may not be evaluated at neighbouring alpha or beta, whose products are left out
+                    let product = s[i] * s[j];
+                    if product.is_finite() {
+                        information[i][j] += w * f * product;
+                    }
+                }
+            }
+        }
+    }
+    Ok(information)
+}
+
+// Density and scores with respect to (alpha, beta, sigma, mu_0) of S^0_alpha(1, beta, 0) at x. None where the pdf vanishes,
+// and scores that cannot be evaluated are not finite.
+fn scores(x: f64, alpha: f64, beta: f64) -> Result<Option<(f64, [f64; 4])>, Error> {
+    let (f, s) = standard_distribution(alpha, beta)?.score_S0(x)?;
+    if f.partial_cmp(&0.0) != Some(Ordering::Greater) || f.is_infinite() {
+        return Ok(None);
+    }
+    Ok(Some((f, s)))
+}
+
+// S^0_alpha(1, beta, 0), with the pdf accepting estimates that fail to reach the integration tolerance far in the tails.
+fn standard_distribution(alpha: f64, beta: f64) -> Result<AlphaStable, Error> {
+    let mut distribution = AlphaStable::new_S0(alpha, beta, 1.0, 0.0)?;
+    distribution.with_integrator(Integrator::new(1e-10, 1e-10, 50, true));
+    Ok(distribution)
+}
+
+// Gauss-Legendre nodes and weights for integration over the support of S^0_alpha(1, beta, 0). The central region is split into
+// panels of equal width. Each tail beyond it is mapped to (0, 1] by x = c v^(-2/alpha), which makes the integrand of a
+// density decaying like |x|^(-1-alpha) vanish linearly at v == 0.
+fn quadrature_nodes(alpha: f64, beta: f64) -> Result<Vec<(f64, f64)>, Error> {
+
+    let (lower, upper) = AlphaStable::new_S0(alpha, beta, 1.0, 0.0)?.support();
+    let (a, b) = (lower.max(-CENTRAL_WIDTH), upper.min(CENTRAL_WIDTH));
+    let (nodes, weights) = gauss_legendre();
+    let mut points = Vec::new();
+
+    let width = (b - a) / CENTRAL_PANELS as f64;
+    for k in 0..CENTRAL_PANELS {
+        let centre = a + (k as f64 + 0.5) * width;
+        for (u, w) in nodes.iter().zip(weights.iter()) {
+            points.push((centre + 0.5 * width * u, 0.5 * width * w));
+        }
+    }
+
+    let m = 2.0 / alpha;
+    let panel = 1.0 / TAIL_PANELS as f64;
+    for (bound, sign) in [(upper, 1.0), (lower, -1.0)] {
+        if bound.abs() <= CENTRAL_WIDTH {
+            continue;
+        }
+        for k in 0..TAIL_PANELS {
+            let centre = (k as f64 + 0.5) * panel;
+            for (u, w) in nodes.iter().zip(weights.iter()) {
+                let v = centre + 0.5 * panel * u;
+                let x = CENTRAL_WIDTH * v.powf(-m);
+                if x.is_finite() {
+                    points.push((sign * x, 0.5 * panel * w * m * x / v));
+                }
+            }
+        }
+    }
+    Ok(points)
+}
+
+// Ten point Gauss-Legendre nodes and weights on [-1, 1], found by Newton's method on the Legendre polynomial.
+fn gauss_legendre() -> ([f64; 10], [f64; 10]) {
+    const N: usize = 10;
+    let mut nodes = [0.0; N];
+    let mut weights = [0.0; N];
+    for i in 0..N {
+        let mut x = (PI * (i as f64 + 0.75) / (N as f64 + 0.5)).cos();
+        let mut derivative = 0.0;
+        for _ in 0..100 {
+            let (mut p_0, mut p_1) = (1.0, x);
+            for k in 2..=N {
+                let p_2 = ((2 * k - 1) as f64 * x * p_1 - (k - 1) as f64 * p_0) / k as f64;
+                p_0 = p_1;
+                p_1 = p_2;
+            }
+            derivative = N as f64 * (x * p_1 - p_0) / (x * x - 1.0);
+            let dx = p_1 / derivative;
+            x -= dx;
+            if dx.abs() < 1e-15 {
+                break;
+            }
+        }
+        nodes[i] = x;
+        weights[i] = 2.0 / ((1.0 - x * x) * derivative * derivative);
+    }
+    (nodes, weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{quadrature_nodes, standard_distribution};
+    use crate::alpha_stable::{close, AlphaStable, Form};
+    use crate::error::Error;
+
+    #[test]
+    fn test_quadrature_mass() {
+        for (alpha, beta) in [(1.5, 0.5), (0.7, 1.0), (1.0, -0.3)] {
+            let dist = standard_distribution(alpha, beta).unwrap();
+            let mass: f64 = quadrature_nodes(alpha, beta).unwrap().iter().map(|(x, w)| w * dist.pdf(*x).unwrap()).sum();
+            assert!(close(mass, 1.0, 1e-6));
+        }
+    }
+
+    #[test]
+    fn test_cauchy_information() {
+        // Location and scale information of a Cauchy distribution are both 1 / (2 sigma^2)
+        let sigma = 2.0;
+        let dist = AlphaStable::new(1.0, 0.0, sigma, 3.0).unwrap();
+        let information = dist.fisher_information(Form::Nolan).unwrap();
+        assert!(close(information[2][2], 0.5 / (sigma * sigma), 1e-6));
+        assert!(close(information[3][3], 0.5 / (sigma * sigma), 1e-6));
+        assert!(close(information[2][3], 0.0, 1e-8));
+        assert!(close(information[0][1], 0.0, 1e-8));
+
+        let errors = dist.standard_errors(Form::Nolan, 100).unwrap();
+        assert!(errors.iter().all(|e| e.is_finite() && *e > 0.0));
+        assert!(matches!(dist.standard_errors(Form::Nolan, 0), Err(Error::SampleSizeError { n: 0 })));
+    }
+
+    #[test]
+    fn test_standard_form() {
+        let (alpha, beta, sigma) = (1.5, 0.5, 1.5);
+        let dist = AlphaStable::new(alpha, beta, sigma, 0.0).unwrap();
+        let nolan = dist.fisher_information(Form::Nolan).unwrap();
+        let standard = dist.fisher_information(Form::Standard).unwrap();
+        let tan = (0.5 * std::f64::consts::PI * alpha).tan();
+        assert!(close(standard[3][3], nolan[3][3], 1e-14));
+        assert!(close(standard[1][3], nolan[1][3] + sigma * tan * nolan[3][3], 1e-12));
+        for (i, row) in standard.iter().enumerate() {
+            for (j, val) in row.iter().enumerate() {
+                assert!(close(*val, standard[j][i], 1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn test_normal_boundary() {
+        // Beta does not affect the distribution when alpha == 2, and alpha is on the boundary
+        let sigma = 1.5;
+        let dist = AlphaStable::new(2.0, 0.5, sigma, 0.0).unwrap();
+        let information = dist.fisher_information(Form::Nolan).unwrap();
+        assert!(information.iter().all(|row| row[0] == 0.0 && row[1] == 0.0));
+        assert!(information[0].iter().chain(&information[1]).all(|val| *val == 0.0));
+        assert!(close(information[2][2], 2.0 / (sigma * sigma), 1e-14));
+        assert!(close(information[3][3], 0.5 / (sigma * sigma), 1e-14));
+
+        for form in [Form::Nolan, Form::Standard] {
+            let errors = dist.standard_errors(form, 50).unwrap();
+            assert!(errors[0].is_nan() && errors[1].is_nan());
+            assert!(close(errors[2], sigma / 100.0_f64.sqrt(), 1e-14));
+            assert!(close(errors[3], sigma * (2.0_f64 / 50.0).sqrt(), 1e-14));
+        }
+    }
+
+    #[test]
+    fn test_near_normal() {
+        // Close to alpha == 2 the information is integrated directly, and that of alpha grows as alpha approaches 2
+        let information = |alpha: f64| AlphaStable::new_S0(alpha, 0.5, 1.0, 0.0).unwrap().fisher_information(Form::Nolan).unwrap();
+        let (near, nearer) = (information(1.97), information(1.99));
+        assert!(near[0][0] > 1.0 && nearer[0][0] > near[0][0] && nearer[0][0] < 10.0);
+        assert!(close(nearer[3][3], 0.5, 0.01));
+        let errors = AlphaStable::new_S0(1.99, 0.5, 1.0, 0.0).unwrap().standard_errors(Form::Nolan, 100).unwrap();
+        assert!(errors.iter().all(|e| e.is_finite() && *e > 0.0));
+    }
+}
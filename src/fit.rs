@@ -0,0 +1,757 @@
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::alpha_stable::{close, AlphaStable, Tol};
+use crate::error::Error;
+use crate::linalg::least_squares;
+use crate::optimize::nelder_mead;
+use crate::special::{normal_cdf, normal_quantile};
+
+/// Fit an alpha stable distribution to data by maximum likelihood.
+///
+/// The likelihood is maximised over the parameters of Nolan's form, which vary continuously in alpha, by the Nelder-Mead
+/// method. The search starts from an empirical characteristic function estimate and is carried out on data centred on the
+/// median and scaled by the interquartile range. Alpha is restricted to [0.1, 2].
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use alpha_stable::{fit::fit_mle, AlphaStable};
+///
+/// let mut rng = thread_rng();
+/// let distribution = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+/// let data: Vec<f64> = (0..100).map(|_| distribution.sample(&mut rng)).collect();
+/// let fitted = fit_mle(&data).unwrap();
+/// ```
+pub fn fit_mle(data: &[f64]) -> Result<AlphaStable, Error> {
+    mle(data, None)
+}
+
+/// Fit an alpha stable distribution to data by maximum likelihood, starting the search from a given alpha, such as a
+/// tail index estimate from the tail module, in place of the empirical characteristic function estimate of alpha.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use alpha_stable::{fit::fit_mle_with_alpha, tail::TailEstimator, AlphaStable};
+///
+/// let mut rng = thread_rng();
+/// let distribution = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+/// let data: Vec<f64> = (0..100).map(|_| distribution.sample(&mut rng)).collect();
+/// let abs: Vec<f64> = data.iter().map(|x| x.abs()).collect();
+/// let alpha = TailEstimator::Hill.estimate(&abs, 10).unwrap().get_alpha();
+/// let fitted = fit_mle_with_alpha(&data, alpha).unwrap();
+/// ```
+pub fn fit_mle_with_alpha(data: &[f64], alpha: f64) -> Result<AlphaStable, Error> {
+    if !(ALPHA_MIN..=2.0).contains(&alpha) {
+        return Err(Error::AlphaError { alpha });
+    }
+    mle(data, Some(alpha))
+}
+
+// Maximum likelihood estimate, with the search started from alpha where given.
+fn mle(data: &[f64], alpha: Option<f64>) -> Result<AlphaStable, Error> {
+
+    if data.len() < MIN_SAMPLE_SIZE {
+        return Err(Error::SampleSizeError { n: data.len() });
+    }
+
+    let (location, scale) = robust_location_scale(data);
+    let y: Vec<f64> = data.iter().map(|x| (x - location) / scale).collect();
+
+    let mut x0 = ecf_estimate(&|t| empirical_cf(&y, t));
+    if let Some(alpha) = alpha {
+        x0[0] = alpha;
+    }
+    let steps = initial_steps(&x0);
+    let f = |p: &[f64]| negative_log_likelihood(p, &y);
+    let (p, _) = nelder_mead(&f, &x0, &steps, MLE_TOL, MLE_ITERATIONS);
+
+    AlphaStable::new_S0(p[0], p[1], p[2].exp() * scale, p[3] * scale + location)
+}
+
+/// Fit an alpha stable distribution to weighted data by maximum likelihood, maximising sum_i w_i ln f(x_i).
+///
+/// As fit_mle, with the data centred on the weighted median and scaled by half the weighted interquartile range, and the
+/// search started from an estimate from the weighted empirical characteristic function. Weights must be non-negative,
+/// and are normalised to sum to one. Observations of zero weight are ignored.
+///
+/// # Example
+///
+/// ```
+/// use alpha_stable::fit::fit_mle_weighted;
+///
+/// let data = [-2.1, -0.3, 0.2, 0.5, 1.1, 4.0, -0.8, 0.0, 0.3, -1.2];
+/// let weights = [1.0, 2.0, 2.0, 1.0, 1.0, 0.5, 1.0, 2.0, 1.0, 1.0];
+/// let fitted = fit_mle_weighted(&data, &weights).unwrap();
+/// ```
+pub fn fit_mle_weighted(data: &[f64], weights: &[f64]) -> Result<AlphaStable, Error> {
+    let weights = normalised_weights(data, weights)?;
+    let (location, scale) = weighted_location_scale(data, &weights);
+    let y: Vec<f64> = data.iter().map(|x| (x - location) / scale).collect();
+    let x0 = ecf_estimate(&|t| weighted_empirical_cf(&y, &weights, t));
+    weighted_mle(&y, &weights, x0, initial_steps(&x0), MLE_ITERATIONS, location, scale)
+}
+
+// As fit_mle_weighted, with the search started from start and limited to iterations, as in the M-step of EM where the
+// previous fit is close to the next.
+pub(crate) fn fit_mle_weighted_from(data: &[f64], weights: &[f64], start: &AlphaStable, iterations: usize) -> Result<AlphaStable, Error> {
+    let weights = normalised_weights(data, weights)?;
+    let (location, scale) = weighted_location_scale(data, &weights);
+    let y: Vec<f64> = data.iter().map(|x| (x - location) / scale).collect();
+    let (alpha, beta, sigma, _, mu_0) = start.get_params();
+    let x0 = [alpha.clamp(ALPHA_MIN, 2.0), beta, (sigma / scale).ln(), (mu_0 - location) / scale];
+    let steps = initial_steps(&x0).map(|step| 0.5 * step);
+    weighted_mle(&y, &weights, x0, steps, iterations, location, scale)
+}
+
+// Weighted maximum likelihood estimate on data centred on location and scaled by scale, from the S0 parameters x0.
+fn weighted_mle(
+    y: &[f64],
+    weights: &[f64],
+    x0: [f64; 4],
+    steps: [f64; 4],
+    iterations: usize,
+    location: f64,
+    scale: f64,
+) -> Result<AlphaStable, Error> {
+    let f = |p: &[f64]| weighted_negative_log_likelihood(p, y, weights);
+    let (p, _) = nelder_mead(&f, &x0, &steps, MLE_TOL, iterations);
+    AlphaStable::new_S0(p[0], p[1], p[2].exp() * scale, p[3] * scale + location)
+}
+
+/// Fit an alpha stable distribution to data by minimising the weighted distance between the empirical and theoretical
+/// characteristic functions, sum_k w_k |phi_n(t_k) - phi(t_k)|^2, over the grid of t_k with weights w_k.
+///
+/// Like maximum likelihood the search is over the parameters of Nolan's form by the Nelder-Mead method, starts from
+/// Press's estimate and is carried out on data centred on the median and scaled by half the interquartile range, in whose
+/// units the grid is given. Since the empirical characteristic function is bounded, the estimate is robust to outliers and
+/// to rounding of the data.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use alpha_stable::{fit::{ecf_grid, fit_ecf}, AlphaStable};
+///
+/// let mut rng = thread_rng();
+/// let distribution = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+/// let data: Vec<f64> = (0..1000).map(|_| distribution.sample(&mut rng)).collect();
+/// let grid = ecf_grid(0.1, 2.0, 20);
+/// let weights: Vec<f64> = grid.iter().map(|t| (-t * t).exp()).collect();
+/// let fitted = fit_ecf(&data, &grid, &weights).unwrap();
+/// ```
+pub fn fit_ecf(data: &[f64], grid: &[f64], weights: &[f64]) -> Result<AlphaStable, Error> {
+
+    if data.len() < MIN_SAMPLE_SIZE {
+        return Err(Error::SampleSizeError { n: data.len() });
+    }
+    check_grid(grid)?;
+    if weights.len() != grid.len() {
+        return Err(Error::LengthMismatchError { expected: grid.len(), found: weights.len() });
+    }
+
+    let (location, scale) = robust_location_scale(data);
+    let y: Vec<f64> = data.iter().map(|x| (x - location) / scale).collect();
+    let phi: Vec<Complex64> = grid.iter().map(|t| empirical_cf(&y, *t)).collect();
+
+    let f = |p: &[f64]| {
+        if p[0] < ALPHA_MIN || p[0] > 2.0 || p[1].abs() > 1.0 {
+            return f64::INFINITY;
+        }
+        let distribution = match AlphaStable::new_S0(p[0], p[1], p[2].exp(), p[3]) {
+            Ok(distribution) => distribution,
+            Err(_) => return f64::INFINITY,
+        };
+        grid.iter().zip(weights.iter()).zip(phi.iter())
+            .map(|((t, w), phi)| w * (phi - distribution.characteristic_function(*t)).norm_sqr())
+            .sum()
+    };
+
+    let x0 = ecf_estimate(&|t| empirical_cf(&y, t));
+    let steps = initial_steps(&x0);
+    let (p, _) = nelder_mead(&f, &x0, &steps, ECF_TOL, ECF_ITERATIONS);
+
+    AlphaStable::new_S0(p[0], p[1], p[2].exp() * scale, p[3] * scale + location)
+}
+
+/// Fit an alpha stable distribution to data by Press's method of moments on the empirical characteristic function at
+/// two points 0 < t_1 < t_2, given in units of half the interquartile range of the data. Alpha and sigma follow from
+/// ln|phi(t)| = -sigma^alpha t^alpha at both points, then beta and mu from the arguments of phi.
+///
+/// # Example
+///
+/// ```
+/// use alpha_stable::fit::fit_ecf_press;
+///
+/// let data = [-2.1, -0.3, 0.2, 0.5, 1.1, 4.0, -0.8, 0.0, 0.3, -1.2];
+/// let fitted = fit_ecf_press(&data, 0.2, 0.8).unwrap();
+/// ```
+pub fn fit_ecf_press(data: &[f64], t_1: f64, t_2: f64) -> Result<AlphaStable, Error> {
+
+    if data.len() < MIN_SAMPLE_SIZE {
+        return Err(Error::SampleSizeError { n: data.len() });
+    }
+    check_grid(&[t_1, t_2])?;
+
+    let (location, scale) = robust_location_scale(data);
+    let y: Vec<f64> = data.iter().map(|x| (x - location) / scale).collect();
+    let p = press_estimate(&|t| empirical_cf(&y, t), t_1, t_2);
+    AlphaStable::new_S0(p[0], p[1], p[2].exp() * scale, p[3] * scale + location)
+}
+
+/// Fit an alpha stable distribution to data by Kogon and Williams' regressions on the empirical characteristic function
+/// over a grid, given in units of half the interquartile range of the data, for which they recommend 10 points from 0.1
+/// to 1.
+///
+/// Alpha and sigma are found by regressing ln(-ln|phi_n(t)|) on ln t, since ln(-ln|phi(t)|) = alpha ln sigma + alpha ln t.
+/// Beta and mu_0 are then found by regressing arg phi_n(t) on t and tan(pi alpha / 2) (sigma^alpha t^alpha - sigma t), to
+/// which it is linear in Nolan's form.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use alpha_stable::{fit::{ecf_grid, fit_ecf_kogon_williams}, AlphaStable};
+///
+/// let mut rng = thread_rng();
+/// let distribution = AlphaStable::new(1.5, 0.5, 1.0, 0.0).unwrap();
+/// let data: Vec<f64> = (0..1000).map(|_| distribution.sample(&mut rng)).collect();
+/// let fitted = fit_ecf_kogon_williams(&data, &ecf_grid(0.1, 1.0, 10)).unwrap();
+/// ```
+pub fn fit_ecf_kogon_williams(data: &[f64], grid: &[f64]) -> Result<AlphaStable, Error> {
+
+    if data.len() < MIN_SAMPLE_SIZE {
+        return Err(Error::SampleSizeError { n: data.len() });
+    }
+    check_grid(grid)?;
+
+    let (location, scale) = robust_location_scale(data);
+    let y: Vec<f64> = data.iter().map(|x| (x - location) / scale).collect();
+    let phi: Vec<(f64, Complex64)> = grid.iter().map(|t| (*t, empirical_cf(&y, *t))).collect();
+
+    // Points where the modulus is 0 or 1 carry no information on alpha
+    let (rows, l): (Vec<Vec<f64>>, Vec<f64>) = phi.iter()
+        .map(|(t, phi)| (vec![1.0, t.ln()], (-phi.norm().ln()).ln()))
+        .filter(|(_, l)| l.is_finite())
+        .unzip();
+    let c = least_squares(&rows, &l).ok_or(Error::SampleSizeError { n: l.len() })?;
+    let alpha = c[1].clamp(ALPHA_MIN, 2.0);
+    let sigma = (c[0] / alpha).exp();
+
+    let tol = Tol::default();
+    let skew = |t: f64| {
+        let u = sigma * t;
+        if close(alpha, 1.0, tol.alpha) {
+            -2.0 * u * u.ln() / PI
+        } else {
+            (0.5 * PI * alpha).tan() * (u.powf(alpha) - u)
+        }
+    };
+    let u: Vec<f64> = phi.iter().map(|(_, phi)| phi.arg()).collect();
+    let rows: Vec<Vec<f64>> = grid.iter().map(|t| vec![*t, skew(*t)]).collect();
+
+    // Skewness has no effect when alpha == 2, where the regression is singular
+    let (mu_0, beta) = match least_squares(&rows, &u) {
+        Some(c) if c[1].is_finite() => (c[0], c[1].clamp(-1.0, 1.0)),
+        _ => (grid.iter().zip(u.iter()).map(|(t, u)| t * u).sum::<f64>() / grid.iter().map(|t| t * t).sum::<f64>(), 0.0),
+    };
+
+    AlphaStable::new_S0(alpha, beta, sigma * scale, mu_0 * scale + location)
+}
+
+/// Grid of n equally spaced points from t_min to t_max, for the estimators based on the empirical characteristic function.
+pub fn ecf_grid(t_min: f64, t_max: f64, n: usize) -> Vec<f64> {
+    if n == 1 {
+        return vec![t_min];
+    }
+    (0..n).map(|k| t_min + (t_max - t_min) * k as f64 / (n - 1) as f64).collect()
+}
+
+/// Log likelihood of data under a distribution.
+///
+/// # Example
+///
+/// ```
+/// use alpha_stable::{fit::log_likelihood, AlphaStable};
+///
+/// let distribution = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+/// let val = log_likelihood(&distribution, &[-0.5, 0.1, 2.0]).unwrap();
+/// ```
+pub fn log_likelihood(distribution: &AlphaStable, data: &[f64]) -> Result<f64, Error> {
+    let mut val = 0.0;
+    for x in data {
+        val += distribution.pdf(*x)?.ln();
+    }
+    Ok(val)
+}
+
+/// Bootstrap confidence intervals for the parameters (alpha, beta, sigma, mu) of a fitted distribution.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfidenceIntervals {
+    level: f64,
+    n_boot: usize,
+    estimate: [f64; 4],
+    percentile: [(f64, f64); 4],
+    bca: [(f64, f64); 4],
+}
+
+impl ConfidenceIntervals {
+
+    /// Return confidence level.
+    pub fn get_level(&self) -> f64 {
+        self.level
+    }
+
+    /// Return number of bootstrap samples.
+    pub fn get_n_boot(&self) -> usize {
+        self.n_boot
+    }
+
+    /// Return the estimates (alpha, beta, sigma, mu) from the data.
+    pub fn get_estimate(&self) -> [f64; 4] {
+        self.estimate
+    }
+
+    /// Return percentile intervals as (lower, upper) for alpha, beta, sigma and mu.
+    pub fn get_percentile(&self) -> [(f64, f64); 4] {
+        self.percentile
+    }
+
+    /// Return bias corrected and accelerated (BCa) intervals as (lower, upper) for alpha, beta, sigma and mu.
+    pub fn get_bca(&self) -> [(f64, f64); 4] {
+        self.bca
+    }
+}
+
+/// Parametric bootstrap confidence intervals for the parameters of an alpha stable distribution fitted by fit_fn.
+///
+/// The distribution fitted to the data is sampled n_boot times with an RNG seeded by seed, and each sample refitted.
+/// Percentile intervals are the quantiles of the refitted parameters. BCa intervals adjust these quantiles for bias and
+/// skewness, with the acceleration estimated by the jackknife, which refits the data once per observation.
+///
+/// # Example
+///
+/// ```
+/// use alpha_stable::{fit::bootstrap_confidence_intervals, AlphaStable};
+///
+/// // Cauchy distribution with location and scale estimated from quartiles
+/// let fit = |data: &[f64]| {
+///     let mut sorted = data.to_vec();
+///     sorted.sort_by(f64::total_cmp);
+///     let n = sorted.len();
+///     AlphaStable::new(1.0, 0.0, 0.5 * (sorted[3 * n / 4] - sorted[n / 4]), sorted[n / 2])
+/// };
+/// let data = [-2.1, -0.3, 0.2, 0.5, 1.1, 4.0, -0.8, 0.0];
+/// let intervals = bootstrap_confidence_intervals(&fit, &data, 99, 0.9, 1).unwrap();
+/// let (lower, upper) = intervals.get_percentile()[3];
+/// ```
+pub fn bootstrap_confidence_intervals(
+    fit_fn: &dyn Fn(&[f64]) -> Result<AlphaStable, Error>,
+    data: &[f64],
+    n_boot: usize,
+    level: f64,
+    seed: u64,
+) -> Result<ConfidenceIntervals, Error> {
+
+    if level <= 0.0 || level >= 1.0 || level.is_nan() {
+        return Err(Error::ProbabilityError { p: level });
+    }
+    if n_boot == 0 {
+        return Err(Error::SampleSizeError { n: n_boot });
+    }
+    if data.len() < 2 {
+        return Err(Error::SampleSizeError { n: data.len() });
+    }
+
+    let fitted = fit_fn(data)?;
+    let estimate = parameters(&fitted);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut replicates = Vec::with_capacity(n_boot);
+    for _ in 0..n_boot {
+        let sample: Vec<f64> = (0..data.len()).map(|_| fitted.sample(&mut rng)).collect();
+        replicates.push(parameters(&fit_fn(&sample)?));
+    }
+
+    let mut jackknife = Vec::with_capacity(data.len());
+    for i in 0..data.len() {
+        let sample: Vec<f64> = data.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, x)| *x).collect();
+        jackknife.push(parameters(&fit_fn(&sample)?));
+    }
+
+    let (z_lower, z_upper) = (normal_quantile(0.5 * (1.0 - level)), normal_quantile(0.5 * (1.0 + level)));
+    let mut percentile = [(0.0, 0.0); 4];
+    let mut bca = [(0.0, 0.0); 4];
+    for k in 0..4 {
+        let mut sorted: Vec<f64> = replicates.iter().map(|r| r[k]).collect();
+        sorted.sort_by(f64::total_cmp);
+        percentile[k] = (sample_quantile(&sorted, 0.5 * (1.0 - level)), sample_quantile(&sorted, 0.5 * (1.0 + level)));
+
+        // Bias correction from the proportion of replicates below the estimate, counting ties as half
+        let below = sorted.iter().map(|x| if *x < estimate[k] { 1.0 } else if *x == estimate[k] { 0.5 } else { 0.0 }).sum::<f64>();
+        let proportion = (below / n_boot as f64).clamp(0.5 / n_boot as f64, 1.0 - 0.5 / n_boot as f64);
+        let z_0 = normal_quantile(proportion);
+
+        // Acceleration from the skewness of the jackknife estimates
+        let mean = jackknife.iter().map(|j| j[k]).sum::<f64>() / data.len() as f64;
+        let d2 = jackknife.iter().map(|j| (mean - j[k]).powi(2)).sum::<f64>();
+        let d3 = jackknife.iter().map(|j| (mean - j[k]).powi(3)).sum::<f64>();
+        let a = if d2 > 0.0 { d3 / (6.0 * d2.powf(1.5)) } else { 0.0 };
+
+        let adjusted = |z: f64| normal_cdf(z_0 + (z_0 + z) / (1.0 - a * (z_0 + z)));
+        bca[k] = (sample_quantile(&sorted, adjusted(z_lower)), sample_quantile(&sorted, adjusted(z_upper)));
+    }
+
+    Ok(ConfidenceIntervals { level, n_boot, estimate, percentile, bca })
+}
+
+// Smallest sample accepted by the estimators.
+pub(crate) const MIN_SAMPLE_SIZE: usize = 5;
+
+// Smallest alpha considered by maximum likelihood, below which the density is not reliably computed.
+const ALPHA_MIN: f64 = 0.1;
+
+// Convergence tolerance of the negative log likelihood.
+const MLE_TOL: f64 = 1e-6;
+
+// Maximum number of Nelder-Mead iterations.
+const MLE_ITERATIONS: usize = 500;
+
+// Convergence tolerance of the weighted distance between characteristic functions, and maximum number of iterations.
+const ECF_TOL: f64 = 1e-12;
+const ECF_ITERATIONS: usize = 2000;
+
+// Points at which the empirical characteristic function gives the estimate that starts searches.
+const PRESS_T: (f64, f64) = (0.2, 0.8);
+
+// Negative log likelihood of data at the S0 parameters (alpha, beta, ln sigma, mu_0). Infinite outside the parameter space.
+pub(crate) fn negative_log_likelihood(p: &[f64], data: &[f64]) -> f64 {
+    if p[0] < ALPHA_MIN || p[0] > 2.0 || p[1].abs() > 1.0 {
+        return f64::INFINITY;
+    }
+    let distribution = match AlphaStable::new_S0(p[0], p[1], p[2].exp(), p[3]) {
+        Ok(distribution) => distribution,
+        Err(_) => return f64::INFINITY,
+    };
+    match log_likelihood(&distribution, data) {
+        Ok(val) if !val.is_nan() => -val,
+        _ => f64::INFINITY,
+    }
+}
+
+// Weighted negative log likelihood of data at the S0 parameters (alpha, beta, ln sigma, mu_0), skipping observations of
+// zero weight. Infinite outside the parameter space.
+fn weighted_negative_log_likelihood(p: &[f64], data: &[f64], weights: &[f64]) -> f64 {
+    if p[0] < ALPHA_MIN || p[0] > 2.0 || p[1].abs() > 1.0 {
+        return f64::INFINITY;
+    }
+    let distribution = match AlphaStable::new_S0(p[0], p[1], p[2].exp(), p[3]) {
+        Ok(distribution) => distribution,
+        Err(_) => return f64::INFINITY,
+    };
+    let mut val = 0.0;
+    for (x, w) in data.iter().zip(weights).filter(|(_, w)| **w > 0.0) {
+        match distribution.pdf(*x) {
+            Ok(pdf) => val -= w * pdf.ln(),
+            Err(_) => return f64::INFINITY,
+        }
+    }
+    if val.is_nan() { f64::INFINITY } else { val }
+}
+
+// Initial Nelder-Mead steps from the S0 parameters x0, directed into the parameter space.
+pub(crate) fn initial_steps(x0: &[f64; 4]) -> [f64; 4] {
+    [
+        if x0[0] > 1.9 { -0.1 } else { 0.1 },
+        if x0[1] > 0.5 { -0.2 } else { 0.2 },
+        0.1,
+        0.1,
+    ]
+}
+
+// Weights normalised to sum to one, checked against data.
+fn normalised_weights(data: &[f64], weights: &[f64]) -> Result<Vec<f64>, Error> {
+    if data.len() < MIN_SAMPLE_SIZE {
+        return Err(Error::SampleSizeError { n: data.len() });
+    }
+    if weights.len() != data.len() {
+        return Err(Error::LengthMismatchError { expected: data.len(), found: weights.len() });
+    }
+    if let Some(weight) = weights.iter().find(|w| !(**w >= 0.0 && w.is_finite())) {
+        return Err(Error::WeightError { weight: *weight });
+    }
+    let total = weights.iter().sum::<f64>();
+    if total <= 0.0 {
+        return Err(Error::WeightError { weight: total });
+    }
+    Ok(weights.iter().map(|w| w / total).collect())
+}
+
+// Weighted median and half the weighted interquartile range, with weights summing to one.
+fn weighted_location_scale(data: &[f64], weights: &[f64]) -> (f64, f64) {
+    let mut sorted: Vec<(f64, f64)> = data.iter().copied().zip(weights.iter().copied()).collect();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // Smallest observation at which the cumulative weight reaches p
+    let quantile = |p: f64| {
+        let mut cumulative = 0.0;
+        for (x, w) in &sorted {
+            cumulative += w;
+            if cumulative >= p {
+                return *x;
+            }
+        }
+        sorted[sorted.len() - 1].0
+    };
+    let location = quantile(0.5);
+    let scale = 0.5 * (quantile(0.75) - quantile(0.25));
+    if scale > 0.0 {
+        (location, scale)
+    } else {
+        (location, 1.0)
+    }
+}
+
+// Quick estimate from the empirical characteristic function, used to start likelihood searches.
+pub(crate) fn initial_estimate(data: &[f64]) -> Result<AlphaStable, Error> {
+    let (location, scale) = robust_location_scale(data);
+    let y: Vec<f64> = data.iter().map(|x| (x - location) / scale).collect();
+    let p = ecf_estimate(&|t| empirical_cf(&y, t));
+    AlphaStable::new_S0(p[0], p[1], p[2].exp() * scale, p[3] * scale + location)
+}
+
+// Median and half the interquartile range, which is sigma for symmetric Cauchy data.
+pub(crate) fn robust_location_scale(data: &[f64]) -> (f64, f64) {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let location = sample_quantile(&sorted, 0.5);
+    let scale = 0.5 * (sample_quantile(&sorted, 0.75) - sample_quantile(&sorted, 0.25));
+    if scale > 0.0 {
+        (location, scale)
+    } else {
+        (location, 1.0)
+    }
+}
+
+// Quantile of sorted data by linear interpolation between order statistics.
+pub(crate) fn sample_quantile(sorted: &[f64], p: f64) -> f64 {
+    let h = p * (sorted.len() - 1) as f64;
+    let i = h.floor() as usize;
+    if i + 1 >= sorted.len() {
+        return sorted[sorted.len() - 1];
+    }
+    sorted[i] + (h - i as f64) * (sorted[i + 1] - sorted[i])
+}
+
+fn parameters(distribution: &AlphaStable) -> [f64; 4] {
+    let (alpha, beta, sigma, mu, _) = distribution.get_params();
+    [alpha, beta, sigma, mu]
+}
+
+// Empirical characteristic function at t.
+pub(crate) fn empirical_cf(data: &[f64], t: f64) -> Complex64 {
+    data.iter().map(|x| Complex64::from_polar(1.0, t * x)).sum::<Complex64>() / data.len() as f64
+}
+
+// Empirical characteristic function at t of data whose observations carry weights summing to one.
+fn weighted_empirical_cf(data: &[f64], weights: &[f64], t: f64) -> Complex64 {
+    data.iter().zip(weights).map(|(x, w)| Complex64::from_polar(*w, t * x)).sum()
+}
+
+// Estimate of the S0 parameters (alpha, beta, ln sigma, mu_0) of data with unit scale from its empirical characteristic
+// function cf, used to start searches. Press's estimate with beta kept away from +-1.
+fn ecf_estimate(cf: &dyn Fn(f64) -> Complex64) -> [f64; 4] {
+    let mut p = press_estimate(cf, PRESS_T.0, PRESS_T.1);
+    p[0] = p[0].max(ALPHA_MIN + 0.2);
+    p[1] = p[1].clamp(-0.9, 0.9);
+    p
+}
+
+// Press's estimate of the S0 parameters (alpha, beta, ln sigma, mu_0) of data with unit scale from its empirical
+// characteristic function cf at two points, using ln|phi(t)| = -sigma^alpha t^alpha and
+// arg phi(t) = mu t + beta sigma^alpha tan(pi alpha / 2) t^alpha.
+fn press_estimate(cf: &dyn Fn(f64) -> Complex64, t_1: f64, t_2: f64) -> [f64; 4] {
+    let (phi_1, phi_2) = (cf(t_1), cf(t_2));
+    let (l_1, l_2) = (-phi_1.norm().ln(), -phi_2.norm().ln());
+
+    let mut alpha = (l_1 / l_2).ln() / (t_1 / t_2).ln();
+    if !alpha.is_finite() {
+        alpha = 1.5;
+    }
+    let alpha = alpha.clamp(ALPHA_MIN, 2.0);
+
+    let mut sigma_alpha = l_2 / t_2.powf(alpha);
+    if !(sigma_alpha.is_finite() && sigma_alpha > 0.0) {
+        sigma_alpha = 1.0;
+    }
+    let sigma = sigma_alpha.powf(1.0 / alpha);
+
+    // Skewness is poorly identified near alpha == 1 and irrelevant at alpha == 2
+    let tan = (0.5 * PI * alpha).tan();
+    if (alpha - 1.0).abs() < 0.1 || alpha > 1.95 {
+        return [alpha, 0.0, sigma.ln(), 0.0];
+    }
+
+    let (u_1, u_2) = (phi_1.arg(), phi_2.arg());
+    let (a_1, a_2) = (t_1.powf(alpha), t_2.powf(alpha));
+    let det = t_1 * a_2 - t_2 * a_1;
+    let mu = (u_1 * a_2 - u_2 * a_1) / det;
+    let b = (t_1 * u_2 - t_2 * u_1) / det;
+    let beta = (b / (sigma_alpha * tan)).clamp(-1.0, 1.0);
+    let mu_0 = mu + beta * sigma * tan;
+
+    if beta.is_finite() && mu_0.is_finite() {
+        [alpha, beta, sigma.ln(), mu_0]
+    } else {
+        [alpha, 0.0, sigma.ln(), 0.0]
+    }
+}
+
+// Checks that a grid of arguments of a characteristic function is non-empty, positive and strictly increasing.
+fn check_grid(grid: &[f64]) -> Result<(), Error> {
+    if grid.first().is_none_or(|t| *t <= 0.0 || t.is_nan()) {
+        return Err(Error::ArgumentGridError { index: 0 });
+    }
+    for i in 1..grid.len() {
+        if grid[i].partial_cmp(&grid[i - 1]) != Some(Ordering::Greater) {
+            return Err(Error::ArgumentGridError { index: i });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{
+        bootstrap_confidence_intervals, ecf_estimate, ecf_grid, empirical_cf, fit_ecf, fit_ecf_kogon_williams, fit_ecf_press, fit_mle, fit_mle_weighted,
+        fit_mle_with_alpha, robust_location_scale, sample_quantile,
+    };
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+
+    #[test]
+    fn test_sample_quantile() {
+        let sorted = [1.0, 2.0, 4.0, 8.0];
+        assert_eq!(sample_quantile(&sorted, 0.0), 1.0);
+        assert_eq!(sample_quantile(&sorted, 0.5), 3.0);
+        assert_eq!(sample_quantile(&sorted, 1.0), 8.0);
+    }
+
+    #[test]
+    fn test_ecf_estimate() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let dist = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        let data: Vec<f64> = (0..5000).map(|_| dist.sample(&mut rng)).collect();
+        let p = ecf_estimate(&|t| empirical_cf(&data, t));
+        assert!(close(p[0], 1.5, 0.1));
+        assert!(close(p[2].exp(), 1.0, 0.1));
+    }
+
+    #[test]
+    fn test_fit_mle() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let dist = AlphaStable::new_S0(1.4, 0.5, 2.0, 1.0).unwrap();
+        let data: Vec<f64> = (0..400).map(|_| dist.sample(&mut rng)).collect();
+        let fitted = fit_mle(&data).unwrap();
+        let (alpha, beta, sigma, _, mu_0) = fitted.get_params();
+        assert!(close(alpha, 1.4, 0.15));
+        assert!(close(beta, 0.5, 0.3));
+        assert!(close(sigma, 2.0, 0.3));
+        assert!(close(mu_0, 1.0, 0.4));
+    }
+
+    #[test]
+    fn test_fit_mle_weighted() {
+        // Integer weights act as repeated observations
+        let mut rng = StdRng::seed_from_u64(25);
+        let dist = AlphaStable::new_S0(1.4, 0.5, 2.0, 1.0).unwrap();
+        let data: Vec<f64> = (0..100).map(|_| dist.sample(&mut rng)).collect();
+        let weights: Vec<f64> = (0..100).map(|i| (1 + i % 3) as f64).collect();
+        let repeated: Vec<f64> = data.iter().zip(&weights).flat_map(|(x, w)| vec![*x; *w as usize]).collect();
+
+        let (alpha, beta, sigma, _, mu_0) = fit_mle_weighted(&data, &weights).unwrap().get_params();
+        let (alpha_r, beta_r, sigma_r, _, mu_0_r) = fit_mle(&repeated).unwrap().get_params();
+        assert!(close(alpha, alpha_r, 0.01));
+        assert!(close(beta, beta_r, 0.02));
+        assert!(close(sigma, sigma_r, 0.01));
+        assert!(close(mu_0, mu_0_r, 0.01));
+
+        assert!(matches!(fit_mle_weighted(&data, &weights[1..]), Err(Error::LengthMismatchError { .. })));
+        assert!(matches!(fit_mle_weighted(&data, &vec![0.0; 100]), Err(Error::WeightError { .. })));
+    }
+
+    #[test]
+    fn test_ecf_estimators() {
+        let mut rng = StdRng::seed_from_u64(16);
+        let dist = AlphaStable::new_S0(1.4, 0.5, 2.0, 1.0).unwrap();
+        let data: Vec<f64> = (0..20000).map(|_| dist.sample(&mut rng)).collect();
+
+        let grid = ecf_grid(0.1, 2.0, 20);
+        let weights: Vec<f64> = grid.iter().map(|t| (-t * t).exp()).collect();
+        let fits = [
+            (fit_ecf(&data, &grid, &weights).unwrap(), 0.1),
+            (fit_ecf_kogon_williams(&data, &ecf_grid(0.1, 1.0, 10)).unwrap(), 0.1),
+            (fit_ecf_press(&data, 0.2, 0.8).unwrap(), 0.2),
+        ];
+        for (fitted, tol) in fits {
+            let (alpha, beta, sigma, _, mu_0) = fitted.get_params();
+            assert!(close(alpha, 1.4, tol));
+            assert!(close(beta, 0.5, 2.0 * tol));
+            assert!(close(sigma, 2.0, tol));
+            assert!(close(mu_0, 1.0, tol));
+        }
+    }
+
+    #[test]
+    fn test_ecf_grid() {
+        assert_eq!(ecf_grid(0.1, 1.0, 10).len(), 10);
+        assert!(close(ecf_grid(0.1, 1.0, 10)[9], 1.0, 1e-15));
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!(matches!(fit_ecf(&data, &[0.2, 0.1], &[1.0, 1.0]), Err(Error::ArgumentGridError { index: 1 })));
+        assert!(matches!(fit_ecf(&data, &[0.1, 0.2], &[1.0]), Err(Error::LengthMismatchError { expected: 2, found: 1 })));
+        assert!(matches!(fit_ecf_kogon_williams(&data, &[]), Err(Error::ArgumentGridError { index: 0 })));
+        assert!(matches!(fit_ecf_press(&data, 0.0, 0.5), Err(Error::ArgumentGridError { index: 0 })));
+    }
+
+    #[test]
+    fn test_sample_size_error() {
+        assert!(matches!(fit_mle(&[1.0, 2.0]), Err(Error::SampleSizeError { n: 2 })));
+        assert!(matches!(fit_mle_with_alpha(&[1.0, 2.0, 3.0, 4.0, 5.0], 2.5), Err(Error::AlphaError { .. })));
+    }
+
+    #[test]
+    fn test_bootstrap_confidence_intervals() {
+        let fit = |data: &[f64]| {
+            let (location, scale) = robust_location_scale(data);
+            AlphaStable::new(1.0, 0.0, scale, location)
+        };
+        let mut rng = StdRng::seed_from_u64(14);
+        let dist = AlphaStable::new(1.0, 0.0, 2.0, 3.0).unwrap();
+        let data: Vec<f64> = (0..200).map(|_| dist.sample(&mut rng)).collect();
+        let intervals = bootstrap_confidence_intervals(&fit, &data, 400, 0.95, 7).unwrap();
+
+        // Parameters that are not estimated give degenerate intervals
+        assert_eq!(intervals.get_percentile()[0], (1.0, 1.0));
+        assert_eq!(intervals.get_bca()[1], (0.0, 0.0));
+
+        for (k, truth) in [(2, 2.0), (3, 3.0)] {
+            for (lower, upper) in [intervals.get_percentile()[k], intervals.get_bca()[k]] {
+                assert!(lower < intervals.get_estimate()[k] && intervals.get_estimate()[k] < upper);
+                assert!(lower < truth && truth < upper);
+            }
+        }
+
+        // Same seed gives the same intervals
+        let again = bootstrap_confidence_intervals(&fit, &data, 400, 0.95, 7).unwrap();
+        assert_eq!(again.get_bca(), intervals.get_bca());
+        assert!(matches!(bootstrap_confidence_intervals(&fit, &data, 10, 1.0, 7), Err(Error::ProbabilityError { .. })));
+    }
+}
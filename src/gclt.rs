@@ -0,0 +1,208 @@
+use std::f64::consts::PI;
+
+use spfunc::gamma::gamma;
+
+use crate::alpha_stable::{close, AlphaStable, Tol};
+use crate::error::Error;
+
+/// Normalisation of a sum S_n of n independent, identically distributed variables, such that (S_n - b_n) / a_n is
+/// distributed approximately, or exactly, as a limiting alpha stable distribution.
+#[derive(Debug, Clone)]
+pub struct Normalisation {
+    a_n: f64,
+    b_n: f64,
+    limit: AlphaStable,
+}
+
+impl Normalisation {
+
+    /// Return the scale a_n.
+    pub fn get_a_n(&self) -> f64 {
+        self.a_n
+    }
+
+    /// Return the centring b_n.
+    pub fn get_b_n(&self) -> f64 {
+        self.b_n
+    }
+
+    /// Return the limiting distribution.
+    pub fn get_limit(&self) -> &AlphaStable {
+        &self.limit
+    }
+
+    /// Return the normalised sum (sum - b_n) / a_n.
+    pub fn normalise(&self, sum: f64) -> f64 {
+        (sum - self.b_n) / self.a_n
+    }
+}
+
+/// Generalised central limit theorem for a sum of n variables with tails P(X > x) ~ c_plus x^(-alpha) and
+/// P(X < -x) ~ c_minus x^(-alpha), 0 < alpha < 2.
+///
+/// With a_n = n^(1/alpha), the limit is S_alpha(sigma, beta, 0) in Standard form, with sigma^alpha = (c_plus + c_minus) / C_alpha,
+/// C_alpha = 2 Gamma(alpha) sin(pi alpha / 2) / pi, and beta = (c_plus - c_minus) / (c_plus + c_minus). The centring is
+/// b_n = 0 for alpha != 1, and b_n = (c_plus - c_minus) n ln n for alpha == 1, which makes the normalisation exact when the
+/// variables are themselves distributed as the limit. Otherwise variables with alpha > 1 must have zero mean, and for
+/// alpha == 1 the limit may be shifted by a constant that depends on more than the tails.
+///
+/// # Example
+///
+/// ```
+/// use alpha_stable::gclt::tail_normalisation;
+///
+/// // Sums of 1000 Pareto variables with P(X > x) = x^(-1/2), x >= 1
+/// let normalisation = tail_normalisation(0.5, 1.0, 0.0, 1000).unwrap();
+/// assert_eq!(normalisation.get_a_n(), 1e6);
+/// let (_, beta, _, _, _) = normalisation.get_limit().get_params();
+/// assert_eq!(beta, 1.0);
+/// ```
+pub fn tail_normalisation(alpha: f64, c_plus: f64, c_minus: f64, n: usize) -> Result<Normalisation, Error> {
+
+    if alpha <= 0.0 || alpha >= 2.0 || alpha.is_nan() {
+        return Err(Error::AlphaError { alpha });
+    }
+    if c_plus < 0.0 || c_minus < 0.0 || c_plus + c_minus <= 0.0 {
+        return Err(Error::TailConstantError { c_plus, c_minus });
+    }
+    if n == 0 {
+        return Err(Error::SampleSizeError { n });
+    }
+
+    let c_alpha = 2.0 * gamma(alpha) * (0.5 * PI * alpha).sin() / PI;
+    let sigma = ((c_plus + c_minus) / c_alpha).powf(1.0 / alpha);
+    let beta = (c_plus - c_minus) / (c_plus + c_minus);
+    let limit = AlphaStable::new(alpha, beta, sigma, 0.0)?;
+
+    let n_f = n as f64;
+    let b_n = if close( alpha, 1.0, Tol::default().alpha ) { (c_plus - c_minus) * n_f * n_f.ln() } else { 0.0 };
+    Ok(Normalisation { a_n: n_f.powf(1.0 / alpha), b_n, limit })
+}
+
+/// Distribution of the sum of n independent copies of distribution: S_alpha(n^(1/alpha) sigma, beta, n mu) in Standard form.
+///
+/// # Example
+///
+/// ```
+/// use alpha_stable::{gclt::aggregate, AlphaStable};
+///
+/// let distribution = AlphaStable::new( 1.5, 0.5, 1.0, 0.1).unwrap();
+/// let (_, _, sigma, mu, _) = aggregate(&distribution, 8).unwrap().get_params();
+/// assert!((sigma - 4.0).abs() < 1e-12 && (mu - 0.8).abs() < 1e-12);
+/// ```
+pub fn aggregate(distribution: &AlphaStable, n: usize) -> Result<AlphaStable, Error> {
+
+    if n == 0 {
+        return Err(Error::SampleSizeError { n });
+    }
+
+    // The sum is distributed as a_n X + b_n
+    let normalisation = aggregate_normalisation(distribution, n)?;
+    Ok(distribution.scale(normalisation.a_n)?.shift(normalisation.b_n))
+}
+
+/// Normalisation of the sum of n independent copies of distribution under which it is distributed exactly as
+/// distribution: a_n = n^(1/alpha) and b_n = (n - a_n) mu, or (2 / pi) sigma beta n ln n when alpha == 1.
+///
+/// # Example
+///
+/// ```
+/// use alpha_stable::{gclt::aggregate_normalisation, AlphaStable};
+///
+/// let distribution = AlphaStable::new( 1.0, 0.0, 1.0, 2.0).unwrap();
+/// let normalisation = aggregate_normalisation(&distribution, 10).unwrap();
+/// assert_eq!(normalisation.normalise(20.0), 2.0);
+/// ```
+pub fn aggregate_normalisation(distribution: &AlphaStable, n: usize) -> Result<Normalisation, Error> {
+
+    if n == 0 {
+        return Err(Error::SampleSizeError { n });
+    }
+
+    let (alpha, beta, sigma, mu, _) = distribution.get_params();
+    let n_f = n as f64;
+    let a_n = n_f.powf(1.0 / alpha);
+    let b_n = if close( alpha, 1.0, distribution.get_tol().alpha ) {
+        2.0 * sigma * beta * n_f * n_f.ln() / PI
+    } else {
+        (n_f - a_n) * mu
+    };
+    Ok(Normalisation { a_n, b_n, limit: distribution.clone() })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{aggregate, aggregate_normalisation, tail_normalisation};
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+    use crate::fit::sample_quantile;
+
+    #[test]
+    fn test_aggregate() {
+        // Sums of n copies are exactly stable, and normalise back to the distribution
+        for alpha in [0.7, 1.0, 1.5] {
+            let dist = AlphaStable::new(alpha, 0.4, 1.5, 0.3).unwrap();
+            let sum = aggregate(&dist, 7).unwrap();
+            for t in [-1.3, 0.4, 2.0_f64] {
+                assert!((sum.characteristic_function(t) - dist.characteristic_function(t).powi(7)).norm() < 1e-13);
+            }
+
+            let normalisation = aggregate_normalisation(&dist, 7).unwrap();
+            let normalised = sum.shift(-normalisation.get_b_n()).scale(1.0 / normalisation.get_a_n()).unwrap();
+            for t in [-1.3, 0.4, 2.0_f64] {
+                assert!((normalised.characteristic_function(t) - dist.characteristic_function(t)).norm() < 1e-13);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tail_normalisation() {
+        // Tail constants of S_alpha(sigma, beta, 0) are C_alpha (1 +- beta) sigma^alpha / 2, and give back the distribution
+        // and the normalisation of its sums
+        for alpha in [0.7, 1.0, 1.5] {
+            let (beta, sigma) = (0.4, 1.5_f64);
+            let c_alpha = 2.0 * spfunc::gamma::gamma(alpha) * (0.5 * PI * alpha).sin() / PI;
+            let c = |sign: f64| 0.5 * c_alpha * (1.0 + sign * beta) * sigma.powf(alpha);
+            let normalisation = tail_normalisation(alpha, c(1.0), c(-1.0), 7).unwrap();
+            let (limit_alpha, limit_beta, limit_sigma, limit_mu, _) = normalisation.get_limit().get_params();
+            assert_eq!(limit_alpha, alpha);
+            assert!(close(limit_beta, beta, 1e-14));
+            assert!(close(limit_sigma, sigma, 1e-14));
+            assert_eq!(limit_mu, 0.0);
+
+            let dist = AlphaStable::new(alpha, beta, sigma, 0.0).unwrap();
+            let exact = aggregate_normalisation(&dist, 7).unwrap();
+            assert!(close(normalisation.get_a_n(), exact.get_a_n(), 1e-14));
+            assert!(close(normalisation.get_b_n(), exact.get_b_n(), 1e-13));
+        }
+    }
+
+    #[test]
+    fn test_pareto_sums() {
+        // Normalised sums of Pareto variables with P(X > x) = x^(-1/2) approach the limit
+        let mut rng = StdRng::seed_from_u64(20);
+        let (alpha, n) = (0.5, 1000);
+        let normalisation = tail_normalisation(alpha, 1.0, 0.0, n).unwrap();
+        let mut sums: Vec<f64> = (0..2000)
+            .map(|_| normalisation.normalise((0..n).map(|_| (1.0 - rng.gen::<f64>()).powf(-1.0 / alpha)).sum()))
+            .collect();
+        sums.sort_by(f64::total_cmp);
+        for p in [0.25, 0.5, 0.75] {
+            let expected = normalisation.get_limit().quantile(p).unwrap();
+            assert!(close(sample_quantile(&sums, p), expected, 0.1 * expected));
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(matches!(tail_normalisation(2.0, 1.0, 1.0, 10), Err(Error::AlphaError { .. })));
+        assert!(matches!(tail_normalisation(1.5, -1.0, 1.0, 10), Err(Error::TailConstantError { .. })));
+        assert!(matches!(tail_normalisation(1.5, 1.0, 1.0, 0), Err(Error::SampleSizeError { n: 0 })));
+        let dist = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        assert!(matches!(aggregate(&dist, 0), Err(Error::SampleSizeError { n: 0 })));
+    }
+}
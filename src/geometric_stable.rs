@@ -0,0 +1,303 @@
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+use rand::Rng;
+use rand_distr::{Distribution, Exp1};
+
+use crate::alpha_stable::{close, AlphaStable, Tol};
+use crate::error::Error;
+use crate::integrator::Integrator;
+
+/// Defines Linnik's distribution, the symmetric geometric stable law with characteristic function
+/// phi(t) = 1 / (1 + sigma^alpha |t|^alpha), 0 < alpha <= 2.
+///
+/// It is the distribution of E^(1/alpha) Y for E exponentially distributed with unit mean and Y distributed as
+/// S_alpha(sigma, 0, 0), and the limit of sums of a geometrically distributed number of symmetric variables in the domain
+/// of attraction of Y. Alpha == 2 gives the Laplace distribution.
+#[derive(Debug, Clone)]
+pub struct Linnik {
+    alpha: f64,
+    sigma: f64,
+    stable: AlphaStable,
+    integrator: Integrator,
+}
+
+impl Linnik {
+
+    /// Create distribution from alpha in (0, 2] and scale sigma.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::Linnik;
+    ///
+    /// let distribution = Linnik::new(1.5, 1.0).unwrap();
+    /// let val = distribution.pdf(0.5).unwrap();
+    /// ```
+    pub fn new(alpha: f64, sigma: f64) -> Result<Linnik, Error> {
+
+        if sigma <= 0.0 || !sigma.is_finite() {
+            return Err(Error::ScaleError { scale: sigma });
+        }
+
+        let stable = AlphaStable::new(alpha, 0.0, sigma, 0.0)?;
+        Ok(Linnik { alpha, sigma, stable, integrator: Integrator::default() })
+    }
+
+    /// Set integration parameters.
+    pub fn with_integrator(&mut self, integrator: Integrator) -> &mut Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// Return parameters as tuple of (alpha, sigma).
+    pub fn get_params(&self) -> (f64, f64) {
+        (self.alpha, self.sigma)
+    }
+
+    /// Characteristic function E[exp(itX)] = 1 / (1 + sigma^alpha |t|^alpha).
+    pub fn characteristic_function(&self, t: f64) -> Complex64 {
+        Complex64::new(1.0 / (1.0 + (self.sigma * t.abs()).powf(self.alpha)), 0.0)
+    }
+
+    /// Value of Probability Distribution function at x, infinite at zero for alpha <= 1.
+    ///
+    /// For alpha < 2 and sigma == 1, f(x) = (sin(pi alpha / 2) / pi) int_0^inf v^alpha exp(-v |x|) / (1 + 2 v^alpha cos(pi alpha / 2) + v^(2 alpha)) dv.
+    pub fn pdf(&self, x: f64) -> Result<f64, Error> {
+        let z = x.abs() / self.sigma;
+        if close( self.alpha, 2.0, Tol::default().alpha ) {
+            return Ok(0.5 * (-z).exp() / self.sigma);
+        }
+        if z == 0.0 && self.alpha <= 1.0 {
+            return Ok(f64::INFINITY);
+        }
+        Ok(mixture_integral(self.alpha, 0.5 * PI * self.alpha, z, 1.0, &self.integrator)? / self.sigma)
+    }
+
+    /// Value of Cumulative Distribution function at x.
+    ///
+    /// For alpha < 2, sigma == 1 and x > 0, 1 - F(x) = (sin(pi alpha / 2) / pi) int_0^inf v^(alpha - 1) exp(-vx) / (1 + 2 v^alpha cos(pi alpha / 2) + v^(2 alpha)) dv.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::Linnik;
+    ///
+    /// let distribution = Linnik::new(1.5, 1.0).unwrap();
+    /// assert!((distribution.cdf(0.0).unwrap() - 0.5).abs() < 1e-10);
+    /// ```
+    pub fn cdf(&self, x: f64) -> Result<f64, Error> {
+        let z = x.abs() / self.sigma;
+        let tail = if close( self.alpha, 2.0, Tol::default().alpha ) {
+            0.5 * (-z).exp()
+        } else if z == 0.0 {
+            0.5
+        } else {
+            mixture_integral(self.alpha, 0.5 * PI * self.alpha, z, 0.0, &self.integrator)?
+        };
+        Ok(if x < 0.0 { tail } else { 1.0 - tail })
+    }
+
+    /// Sample from the distribution as E^(1/alpha) Y.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let e: f64 = Exp1.sample(rng);
+        e.powf(1.0 / self.alpha) * self.stable.sample(rng)
+    }
+}
+
+/// Defines the Mittag-Leffler distribution, the positive geometric stable law with Laplace transform
+/// E[exp(-sX)] = 1 / (1 + (sigma s)^alpha), 0 < alpha <= 1, and distribution function F(x) = 1 - E_alpha(-(x / sigma)^alpha)
+/// where E_alpha is the Mittag-Leffler function.
+///
+/// It is the distribution of E^(1/alpha) S for E exponentially distributed with unit mean and S the positive, totally
+/// skewed alpha stable variable with E[exp(-sS)] = exp(-(sigma s)^alpha), which is S_alpha(sigma cos(pi alpha / 2)^(1/alpha), 1, 0)
+/// in Standard form. Alpha == 1 gives the exponential distribution with mean sigma. For alpha < 1 the mean is infinite.
+#[derive(Debug, Clone)]
+pub struct MittagLeffler {
+    alpha: f64,
+    sigma: f64,
+    stable: Option<AlphaStable>,
+    integrator: Integrator,
+}
+
+impl MittagLeffler {
+
+    /// Create distribution from alpha in (0, 1] and scale sigma.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::MittagLeffler;
+    ///
+    /// let distribution = MittagLeffler::new(0.7, 1.0).unwrap();
+    /// let val = distribution.cdf(2.0).unwrap();
+    /// ```
+    pub fn new(alpha: f64, sigma: f64) -> Result<MittagLeffler, Error> {
+
+        if alpha <= 0.0 || alpha > 1.0 || alpha.is_nan() {
+            return Err(Error::UnitAlphaError { alpha });
+        }
+
+        if sigma <= 0.0 || !sigma.is_finite() {
+            return Err(Error::ScaleError { scale: sigma });
+        }
+
+        let stable = if close( alpha, 1.0, Tol::default().alpha ) {
+            None
+        } else {
+            Some(AlphaStable::new(alpha, 1.0, sigma * (0.5 * PI * alpha).cos().powf(1.0 / alpha), 0.0)?)
+        };
+        Ok(MittagLeffler { alpha, sigma, stable, integrator: Integrator::default() })
+    }
+
+    /// Set integration parameters.
+    pub fn with_integrator(&mut self, integrator: Integrator) -> &mut Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// Return parameters as tuple of (alpha, sigma).
+    pub fn get_params(&self) -> (f64, f64) {
+        (self.alpha, self.sigma)
+    }
+
+    /// Characteristic function E[exp(itX)] = 1 / (1 + (-i sigma t)^alpha).
+    pub fn characteristic_function(&self, t: f64) -> Complex64 {
+        let s = Complex64::from_polar((self.sigma * t.abs()).powf(self.alpha), -0.5 * PI * self.alpha * t.signum());
+        1.0 / (1.0 + s)
+    }
+
+    /// Value of Probability Distribution function at x, infinite at zero for alpha < 1.
+    ///
+    /// For alpha < 1, sigma == 1 and x > 0, f(x) = (sin(pi alpha) / pi) int_0^inf v^alpha exp(-vx) / (1 + 2 v^alpha cos(pi alpha) + v^(2 alpha)) dv.
+    pub fn pdf(&self, x: f64) -> Result<f64, Error> {
+        let z = x / self.sigma;
+        if z < 0.0 {
+            return Ok(0.0);
+        }
+        if self.stable.is_none() {
+            return Ok((-z).exp() / self.sigma);
+        }
+        if z == 0.0 {
+            return Ok(f64::INFINITY);
+        }
+        Ok(mixture_integral(self.alpha, PI * self.alpha, z, 1.0, &self.integrator)? / self.sigma)
+    }
+
+    /// Value of Cumulative Distribution function at x.
+    ///
+    /// For alpha < 1, sigma == 1 and x > 0, 1 - F(x) = E_alpha(-x^alpha) = (sin(pi alpha) / pi) int_0^inf v^(alpha - 1) exp(-vx) / (1 + 2 v^alpha cos(pi alpha) + v^(2 alpha)) dv.
+    pub fn cdf(&self, x: f64) -> Result<f64, Error> {
+        let z = x / self.sigma;
+        if z <= 0.0 {
+            return Ok(0.0);
+        }
+        if self.stable.is_none() {
+            return Ok(-(-z).exp_m1());
+        }
+        Ok(1.0 - mixture_integral(self.alpha, PI * self.alpha, z, 0.0, &self.integrator)?)
+    }
+
+    /// Sample from the distribution as E^(1/alpha) S.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let e: f64 = Exp1.sample(rng);
+        match &self.stable {
+            Some(stable) => e.powf(1.0 / self.alpha) * stable.sample(rng),
+            None => self.sigma * e,
+        }
+    }
+}
+
+// (sin(theta) / pi) int_0^inf v^(alpha - 1 + k) exp(-vx) / (1 + 2 v^alpha cos(theta) + v^(2 alpha)) dv. Substituting
+// u = v^alpha removes the singularity at zero and the slow decay for small alpha, giving
+// (sin(theta) / (alpha pi)) int_0^inf u^(k / alpha) exp(-x u^(1/alpha)) / (1 + 2u cos(theta) + u^2) du, whose range is
+// split at u == 1, near which the denominator is smallest, and mapped onto [0, 1] above it by u -> 1 / u.
+fn mixture_integral(alpha: f64, theta: f64, x: f64, k: f64, integrator: &Integrator) -> Result<f64, Error> {
+    let (sin, cos) = theta.sin_cos();
+    let g = |u: f64, w: f64| {
+        let val = u.powf(k / alpha) * (-x * u.powf(1.0 / alpha)).exp() / (1.0 + 2.0 * u * cos + u * u) * w;
+        if val.is_finite() { val } else { 0.0 }
+    };
+    let lower = integrator.integrate_range(&|u| g(u, 1.0), 0.0, 1.0)?;
+    let upper = integrator.integrate_range(&|w| g(1.0 / w, 1.0 / (w * w)), 0.0, 1.0)?;
+    Ok(sin * (lower + upper) / (alpha * PI))
+}
+
+#[cfg(test)]
+mod tests {
+    use gkquad::single::integral;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{Linnik, MittagLeffler};
+    use crate::alpha_stable::close;
+    use crate::error::Error;
+    use crate::fit::empirical_cf;
+    use crate::special::erfc;
+
+    #[test]
+    fn test_laplace_and_exponential() {
+        let linnik = Linnik::new(2.0, 1.5).unwrap();
+        let exponential = MittagLeffler::new(1.0, 1.5).unwrap();
+        for x in [-2.0, 0.3, 4.0_f64] {
+            assert!(close(linnik.pdf(x).unwrap(), (-x.abs() / 1.5).exp() / 3.0, 1e-15));
+            if x > 0.0 {
+                assert!(close(exponential.cdf(x).unwrap(), 1.0 - (-x / 1.5).exp(), 1e-15));
+                assert!(close(exponential.pdf(x).unwrap(), (-x / 1.5).exp() / 1.5, 1e-15));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mittag_leffler_half() {
+        // E_1/2(-z) = exp(z^2) erfc(z), so that F(x) = 1 - exp(x) erfc(sqrt(x)) for alpha == 1/2 and sigma == 1
+        let dist = MittagLeffler::new(0.5, 2.0).unwrap();
+        for x in [0.01, 0.5, 3.0, 20.0_f64] {
+            let z = x / 2.0;
+            assert!(close(dist.cdf(x).unwrap(), 1.0 - z.exp() * erfc(z.sqrt()), 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_pdf_integrates_to_cdf() {
+        let linnik = Linnik::new(1.2, 1.5).unwrap();
+        let mittag_leffler = MittagLeffler::new(0.7, 1.5).unwrap();
+        assert!(close(linnik.cdf(0.0).unwrap(), 0.5, 1e-15));
+        for (a, b) in [(-3.0, -0.5), (0.5, 2.0), (1.0, 10.0)] {
+            let mass = integral(|x: f64| linnik.pdf(x).unwrap(), a..b).estimate().unwrap();
+            assert!(close(linnik.cdf(b).unwrap() - linnik.cdf(a).unwrap(), mass, 1e-8));
+            if a > 0.0 {
+                let mass = integral(|x: f64| mittag_leffler.pdf(x).unwrap(), a..b).estimate().unwrap();
+                assert!(close(mittag_leffler.cdf(b).unwrap() - mittag_leffler.cdf(a).unwrap(), mass, 1e-8));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        // Samples match the characteristic function and the cdf
+        let mut rng = StdRng::seed_from_u64(22);
+        let linnik = Linnik::new(1.2, 1.5).unwrap();
+        let mittag_leffler = MittagLeffler::new(0.7, 1.5).unwrap();
+        let n = 20000;
+        let linnik_samples: Vec<f64> = (0..n).map(|_| linnik.sample(&mut rng)).collect();
+        let mittag_leffler_samples: Vec<f64> = (0..n).map(|_| mittag_leffler.sample(&mut rng)).collect();
+        for t in [0.2, 0.7, 1.5] {
+            assert!((empirical_cf(&linnik_samples, t) - linnik.characteristic_function(t)).norm() < 0.02);
+            assert!((empirical_cf(&mittag_leffler_samples, t) - mittag_leffler.characteristic_function(t)).norm() < 0.02);
+        }
+        for x in [0.5, 2.0, 10.0] {
+            let below = mittag_leffler_samples.iter().filter(|s| **s <= x).count() as f64 / n as f64;
+            assert!(close(below, mittag_leffler.cdf(x).unwrap(), 0.015));
+            let below = linnik_samples.iter().filter(|s| **s <= -x).count() as f64 / n as f64;
+            assert!(close(below, linnik.cdf(-x).unwrap(), 0.015));
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(matches!(Linnik::new(2.5, 1.0), Err(Error::AlphaError { .. })));
+        assert!(matches!(Linnik::new(1.5, 0.0), Err(Error::ScaleError { .. })));
+        assert!(matches!(MittagLeffler::new(1.5, 1.0), Err(Error::UnitAlphaError { .. })));
+        assert!(matches!(MittagLeffler::new(0.5, -1.0), Err(Error::ScaleError { .. })));
+    }
+}
@@ -0,0 +1,198 @@
+use rand::Rng;
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+
+/// Goodness of fit statistics based on the empirical distribution function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Statistic {
+    /// Kolmogorov-Smirnov: sup |F_n(x) - F(x)|.
+    KolmogorovSmirnov,
+    /// Cramer-von Mises: n times the integral of (F_n - F)^2 dF.
+    CramerVonMises,
+    /// Anderson-Darling: n times the integral of (F_n - F)^2 / (F (1 - F)) dF, which weights the tails.
+    AndersonDarling,
+}
+
+impl Statistic {
+
+    /// Value of the statistic for data under a distribution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::{gof::Statistic, AlphaStable};
+    ///
+    /// let distribution = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let val = Statistic::AndersonDarling.value(&distribution, &[-0.5, 0.1, 2.0]).unwrap();
+    /// ```
+    pub fn value(&self, distribution: &AlphaStable, data: &[f64]) -> Result<f64, Error> {
+
+        if data.is_empty() {
+            return Err(Error::SampleSizeError { n: 0 });
+        }
+
+        let mut sorted = data.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let mut u = Vec::with_capacity(sorted.len());
+        for x in sorted {
+            u.push(distribution.cdf(x)?);
+        }
+
+        let n = u.len() as f64;
+        let val = match self {
+            Statistic::KolmogorovSmirnov => {
+                u.iter().enumerate().map(|(i, u_i)| (u_i - i as f64 / n).max((i + 1) as f64 / n - u_i)).fold(0.0, f64::max)
+            },
+            Statistic::CramerVonMises => {
+                1.0 / (12.0 * n) + u.iter().enumerate().map(|(i, u_i)| (u_i - (2 * i + 1) as f64 / (2.0 * n)).powi(2)).sum::<f64>()
+            },
+            Statistic::AndersonDarling => {
+                let u: Vec<f64> = u.iter().map(|u_i| u_i.clamp(CDF_FLOOR, 1.0 - CDF_FLOOR)).collect();
+                let s: f64 = u.iter().zip(u.iter().rev()).enumerate()
+                    .map(|(i, (u_i, u_j))| (2 * i + 1) as f64 * (u_i.ln() + (-u_j).ln_1p()))
+                    .sum();
+                -n - s / n
+            },
+        };
+        Ok(val)
+    }
+}
+
+/// Result of a goodness of fit test.
+#[derive(Debug, Clone, Copy)]
+pub struct GofResult {
+    statistic: Statistic,
+    value: f64,
+    p_value: f64,
+    n_boot: usize,
+}
+
+impl GofResult {
+
+    /// Return the statistic used.
+    pub fn get_statistic(&self) -> Statistic {
+        self.statistic
+    }
+
+    /// Return the value of the statistic for the data.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Return the bootstrap p-value.
+    pub fn get_p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    /// Return the number of bootstrap samples.
+    pub fn get_n_boot(&self) -> usize {
+        self.n_boot
+    }
+}
+
+/// Test whether data are drawn from a fully specified distribution.
+///
+/// The p-value is estimated by parametric bootstrap as (1 + b) / (1 + n_boot), where b is the number of samples of the
+/// same size drawn from the distribution whose statistic is at least that of the data.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use alpha_stable::{gof::{gof_test, Statistic}, AlphaStable};
+///
+/// let mut rng = thread_rng();
+/// let distribution = AlphaStable::new(1.0, 0.0, 1.0, 0.0).unwrap();
+/// let data: Vec<f64> = (0..50).map(|_| distribution.sample(&mut rng)).collect();
+/// let result = gof_test(&distribution, &data, Statistic::KolmogorovSmirnov, 99, &mut rng).unwrap();
+/// ```
+pub fn gof_test<R: Rng + ?Sized>(distribution: &AlphaStable, data: &[f64], statistic: Statistic, n_boot: usize, rng: &mut R) -> Result<GofResult, Error> {
+    gof_test_with_refit(data, statistic, n_boot, &|_: &[f64]| Ok(distribution.clone()), rng)
+}
+
+/// Test whether data are drawn from an alpha stable distribution with parameters estimated by fit.
+///
+/// The distribution is fitted to the data and the statistic computed under the fit. Each bootstrap sample is drawn from
+/// the fitted distribution and refitted, so that the p-value accounts for the estimation of the parameters.
+pub fn gof_test_with_refit<R: Rng + ?Sized>(
+    data: &[f64],
+    statistic: Statistic,
+    n_boot: usize,
+    fit: &dyn Fn(&[f64]) -> Result<AlphaStable, Error>,
+    rng: &mut R,
+) -> Result<GofResult, Error> {
+
+    let fitted = fit(data)?;
+    let value = statistic.value(&fitted, data)?;
+
+    let mut exceed = 0;
+    for _ in 0..n_boot {
+        let sample: Vec<f64> = (0..data.len()).map(|_| fitted.sample(rng)).collect();
+        let refitted = fit(&sample)?;
+        if statistic.value(&refitted, &sample)? >= value {
+            exceed += 1;
+        }
+    }
+
+    let p_value = (1 + exceed) as f64 / (1 + n_boot) as f64;
+    Ok(GofResult { statistic, value, p_value, n_boot })
+}
+
+// Values of the distribution function are kept this far from 0 and 1 so the Anderson-Darling statistic is finite.
+const CDF_FLOOR: f64 = 1e-16;
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    use super::{gof_test, gof_test_with_refit, Statistic};
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+    use crate::fit::robust_location_scale;
+
+    #[test]
+    fn test_statistics() {
+        // Standard Cauchy quartiles map to 1/4, 1/2, 3/4
+        let dist = AlphaStable::new(1.0, 0.0, 1.0, 0.0).unwrap();
+        let data = [1.0, -1.0, 0.0];
+        assert!(close(Statistic::KolmogorovSmirnov.value(&dist, &data).unwrap(), 0.25, 1e-12));
+        assert!(close(Statistic::CramerVonMises.value(&dist, &data).unwrap(), 1.0 / 36.0 + 2.0 / 144.0, 1e-12));
+        let ad = -3.0 - (2.0 * 0.25_f64.ln() + 6.0 * 0.5_f64.ln() + 10.0 * 0.75_f64.ln()) / 3.0;
+        assert!(close(Statistic::AndersonDarling.value(&dist, &data).unwrap(), ad, 1e-12));
+        assert!(matches!(Statistic::KolmogorovSmirnov.value(&dist, &[]), Err(Error::SampleSizeError { n: 0 })));
+    }
+
+    #[test]
+    fn test_bootstrap_p_values() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let dist = AlphaStable::new(1.0, 0.0, 1.0, 0.0).unwrap();
+
+        let data: Vec<f64> = (0..100).map(|_| dist.sample(&mut rng)).collect();
+        let result = gof_test(&dist, &data, Statistic::AndersonDarling, 99, &mut rng).unwrap();
+        assert!(result.get_p_value() > 0.05);
+        assert_eq!(result.get_n_boot(), 99);
+        assert_eq!(result.get_statistic(), Statistic::AndersonDarling);
+
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let data: Vec<f64> = (0..100).map(|_| normal.sample(&mut rng)).collect();
+        let result = gof_test(&dist, &data, Statistic::KolmogorovSmirnov, 99, &mut rng).unwrap();
+        assert!(result.get_p_value() < 0.05);
+    }
+
+    #[test]
+    fn test_refit() {
+        // Cauchy with location and scale estimated by the median and half the interquartile range
+        let fit = |data: &[f64]| {
+            let (location, scale) = robust_location_scale(data);
+            AlphaStable::new(1.0, 0.0, scale, location)
+        };
+        let mut rng = StdRng::seed_from_u64(13);
+        let dist = AlphaStable::new(1.0, 0.0, 2.0, 5.0).unwrap();
+        let data: Vec<f64> = (0..100).map(|_| dist.sample(&mut rng)).collect();
+        let result = gof_test_with_refit(&data, Statistic::CramerVonMises, 49, &fit, &mut rng).unwrap();
+        assert!(result.get_p_value() > 0.05);
+        assert!(result.get_value() > 0.0);
+    }
+}
@@ -1,4 +1,4 @@
-use gkquad::{single::Integrator as GKQIntegrator, Tolerance};
+use gkquad::{single::Integrator as GKQIntegrator, IntegrationResult, Tolerance};
 
 use crate::bisect::bisect;
 use crate::error::Error;
@@ -8,7 +8,7 @@ use crate::error::Error;
 /// - eps_bisect - defines convergence tolerance for bisection routine used to find peak of integrand.
 /// - limit_bisect - maximum number of bisection iterations
 /// - continue_on_err - if set to true, integration will not error even if limit_bisect is exceeded or integral does not converge. This code is marked as 'unsafe'.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Integrator {
     eps_quad: f64,
     eps_bisect: f64,
@@ -30,33 +30,71 @@ impl Default for Integrator {
 }
 
 impl Integrator {
+    // Integrates g over [a, b], where g peaks at the root of f and f is the logarithm of a quantity that crosses 1 there.
     pub(crate) fn integrate(&self, f: &dyn Fn(f64) -> f64, g: &dyn Fn(f64) -> f64, a: f64, b:f64) -> Result<f64, Error>  {
+        self.integrate_split(f, g, a, b, Tolerance::Relative(self.eps_quad))
+    }
+
+    // As integrate, for g that changes sign, whose integral may vanish and so cannot be found to a relative tolerance.
+    pub(crate) fn integrate_signed(&self, f: &dyn Fn(f64) -> f64, g: &dyn Fn(f64) -> f64, a: f64, b:f64) -> Result<f64, Error>  {
+        self.integrate_split(f, g, a, b, Tolerance::AbsOrRel(self.eps_quad, self.eps_quad))
+    }
+
+    fn integrate_split(&self, f: &dyn Fn(f64) -> f64, g: &dyn Fn(f64) -> f64, a: f64, b:f64, tolerance: Tolerance) -> Result<f64, Error>  {
+
+        let mut integrator = GKQIntegrator::new(g).tolerance(tolerance);
 
-        let mut integrator = GKQIntegrator::new(g).tolerance(Tolerance::Relative(self.eps_quad));
+        let bisection = bisect(&f, a, b, self.eps_bisect, self.limit_bisect);
+        let max = if self.continue_on_err {
+            unsafe { bisection.estimate_unchecked() }
+        } else {
+            bisection.estimate()?
+        };
+
+        // A peak at an end point needs no split, and splitting there would leave a sub-range too small to integrate
+        if max - a.min(b) <= 2.0 * self.eps_bisect || a.max(b) - max <= 2.0 * self.eps_bisect {
+            return self.estimate(integrator.run(a..b));
+        }
 
+        // The integrand is concentrated within the distance from the peak to the nearer end point, and within the
+        // distance over which f changes by one. Sub-ranges double in width away from the peak, so that a long sub-range
+        // where the integrand is small is not mistaken for one where it vanishes.
+        let (lower, upper) = (a.min(b), a.max(b));
+        let distance = (max - lower).min(upper - max);
+        let h = 1e-3 * distance;
+        let slope = (f(max + h) - f(max - h)) / (2.0 * h);
+        let scale = if slope.is_finite() && slope != 0.0 { distance.min(1.0 / slope.abs()) } else { distance };
+        let mut val = 0.0;
+        for end in [lower, upper] {
+            let (mut inner, mut width) = (max, scale);
+            loop {
+                let outer = if (end - inner).abs() <= 2.0 * width { end } else { inner + width * (end - inner).signum() };
+                val += self.estimate(integrator.run(inner.min(outer)..inner.max(outer)))?;
+                if outer == end {
+                    break;
+                }
+                inner = outer;
+                width *= 2.0;
+            }
+        }
+        Ok(val)
+    }
+
+    // Integrates g over [a, b] without splitting at a peak. Either end may be infinite.
+    pub(crate) fn integrate_range(&self, g: &dyn Fn(f64) -> f64, a: f64, b: f64) -> Result<f64, Error> {
+
+        let mut integrator = GKQIntegrator::new(g).tolerance(Tolerance::AbsOrRel(self.eps_quad, self.eps_quad));
+
+        self.estimate(integrator.run(a..b))
+    }
+
+    // Extracts the estimate from an integration result. Errors are ignored if continue_on_err is set.
+    fn estimate(&self, result: IntegrationResult) -> Result<f64, Error> {
         if self.continue_on_err {
             unsafe {
-                let max = bisect(&f, a, b, self.eps_bisect, self.limit_bisect).estimate_unchecked();
-                let i_1 = integrator.run(a..max).estimate_unchecked();
-                let i_2 = integrator.run(max..b).estimate_unchecked();    
-                return Ok(i_1 + i_2);
+                return Ok(result.estimate_unchecked());
             }
-        } else {
-            let max = bisect(&f, a, b, self.eps_bisect, self.limit_bisect).estimate()?;
-            match (integrator.run(a..max).estimate(), integrator.run(max..b).estimate()) {
-                (Ok(i_1), Ok(i_2)) => {
-                    return Ok(i_1 + i_2);
-                },
-                (Ok(_), Err(e)) => {
-                    return Err(Error::GkQuad { source: e });
-                },
-                (Err(e), Ok(_)) => {
-                    return Err(Error::GkQuad { source: e });
-                },
-                (Err(e), Err(_)) => {
-                    return Err(Error::GkQuad { source: e });
-                }
-            }    
         }
+        Ok(result.estimate()?)
     }
 }
@@ -0,0 +1,205 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+use spfunc::gamma::gamma;
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+
+/// Law of the step lengths of a Levy flight.
+#[derive(Debug, Clone, Copy)]
+pub enum StepLength {
+    /// |X| with X ~ S_alpha(sigma, 0, 0).
+    Stable { alpha: f64, sigma: f64 },
+    /// Pareto law with P(L > l) = (l_min / l)^alpha for l >= l_min.
+    Pareto { alpha: f64, l_min: f64 },
+    /// Mantegna's algorithm: scale |u| / |v|^(1/alpha), with u ~ N(0, sigma_u^2) and v ~ N(0, 1), and sigma_u chosen so
+    /// the tail matches that of a symmetric stable law with unit scale, as used in cuckoo search and related metaheuristics.
+    Mantegna { alpha: f64, scale: f64 },
+}
+
+/// Levy flight: a random walk in d dimensions whose steps have independent lengths drawn from a heavy tailed law
+/// and directions drawn uniformly from the unit sphere.
+#[derive(Debug)]
+pub struct LevyFlight {
+    dimension: usize,
+    step: StepLength,
+    sampler: Sampler,
+}
+
+// Step length law with the quantities that sampling needs computed once.
+#[derive(Debug)]
+enum Sampler {
+    Stable(AlphaStable),
+    Pareto { alpha: f64, l_min: f64 },
+    Mantegna { alpha: f64, sigma_u: f64 },
+}
+
+impl LevyFlight {
+
+    /// Create Levy flight in the given dimension with the given law of step lengths.
+    pub fn new(dimension: usize, step: StepLength) -> Result<LevyFlight, Error> {
+        if dimension == 0 {
+            return Err(Error::DimensionError { dimension });
+        }
+        let (alpha, scale, alpha_valid) = match step {
+            StepLength::Stable { alpha, sigma } => (alpha, sigma, alpha > 0.0 && alpha <= 2.0),
+            StepLength::Pareto { alpha, l_min } => (alpha, l_min, alpha > 0.0),
+            StepLength::Mantegna { alpha, scale } => (alpha, scale, alpha > 0.0 && alpha < 2.0),
+        };
+        if !alpha_valid {
+            return Err(Error::AlphaError { alpha });
+        }
+        if scale <= 0.0 || scale.is_nan() {
+            return Err(Error::ScaleError { scale });
+        }
+        let sampler = match step {
+            StepLength::Stable { alpha, sigma } => Sampler::Stable(AlphaStable::new(alpha, 0.0, sigma, 0.0)?),
+            StepLength::Pareto { alpha, l_min } => Sampler::Pareto { alpha, l_min },
+            StepLength::Mantegna { alpha, scale } => Sampler::Mantegna { alpha, sigma_u: scale * mantegna_sigma(alpha) },
+        };
+        Ok(LevyFlight { dimension, step, sampler })
+    }
+
+    /// Return dimension.
+    pub fn get_dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Return law of step lengths.
+    pub fn get_step(&self) -> StepLength {
+        self.step
+    }
+
+    /// Sample a step length.
+    pub fn sample_step_length<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self.sampler {
+            Sampler::Stable(ref distribution) => distribution.sample(rng).abs(),
+            Sampler::Pareto { alpha, l_min } => {
+                let u = 1.0 - rng.gen::<f64>();
+                l_min * u.powf(-1.0 / alpha)
+            },
+            Sampler::Mantegna { alpha, sigma_u } => {
+                let u: f64 = rng.sample(StandardNormal);
+                let v: f64 = rng.sample(StandardNormal);
+                sigma_u * u.abs() / v.abs().powf(1.0 / alpha)
+            },
+        }
+    }
+
+    /// Sample a direction uniformly from the unit sphere, by normalising a standard normal vector.
+    pub fn sample_direction<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        loop {
+            let v: Vec<f64> = (0..self.dimension).map(|_| StandardNormal.sample(rng)).collect();
+            let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if norm > 0.0 {
+                return v.iter().map(|x| x / norm).collect();
+            }
+        }
+    }
+
+    /// Sample a step: a step length times a uniformly random direction.
+    pub fn sample_step<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let length = self.sample_step_length(rng);
+        self.sample_direction(rng).iter().map(|x| length * x).collect()
+    }
+
+    /// Sample a trajectory of n steps from start, returning the n + 1 positions visited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::levy_flight::{LevyFlight, StepLength};
+    ///
+    /// let mut rng = thread_rng();
+    /// let flight = LevyFlight::new(2, StepLength::Stable { alpha: 1.5, sigma: 1.0 }).unwrap();
+    /// let positions = flight.sample_trajectory(&[0.0, 0.0], 1000, &mut rng).unwrap();
+    /// ```
+    pub fn sample_trajectory<R: Rng + ?Sized>(&self, start: &[f64], n: usize, rng: &mut R) -> Result<Vec<Vec<f64>>, Error> {
+
+        if start.len() != self.dimension {
+            return Err(Error::LengthMismatchError { expected: self.dimension, found: start.len() });
+        }
+
+        let mut positions = Vec::with_capacity(n + 1);
+        positions.push(start.to_vec());
+        for k in 0..n {
+            let step = self.sample_step(rng);
+            let position = positions[k].iter().zip(step.iter()).map(|(x, s)| x + s).collect();
+            positions.push(position);
+        }
+        Ok(positions)
+    }
+}
+
+// Scale of the numerator of Mantegna's algorithm.
+fn mantegna_sigma(alpha: f64) -> f64 {
+    let num = gamma(1.0 + alpha) * (0.5 * PI * alpha).sin();
+    let den = gamma(0.5 * (1.0 + alpha)) * alpha * 2.0_f64.powf(0.5 * (alpha - 1.0));
+    (num / den).powf(1.0 / alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{mantegna_sigma, LevyFlight, StepLength};
+    use crate::alpha_stable::close;
+    use crate::error::Error;
+
+    #[test]
+    fn test_mantegna_sigma() {
+        // Value used in Yang and Deb's cuckoo search
+        assert!(close(mantegna_sigma(1.5), 0.6966, 1e-4));
+    }
+
+    #[test]
+    fn test_pareto_tail() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let flight = LevyFlight::new(3, StepLength::Pareto { alpha: 1.2, l_min: 0.5 }).unwrap();
+        let n = 100000;
+        let lengths: Vec<f64> = (0..n).map(|_| flight.sample_step_length(&mut rng)).collect();
+        assert!(lengths.iter().all(|l| *l >= 0.5));
+        let exceed = lengths.iter().filter(|l| **l > 2.0).count() as f64 / n as f64;
+        assert!(close(exceed, 0.25_f64.powf(1.2), 0.005));
+    }
+
+    #[test]
+    fn test_directions_are_uniform() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let flight = LevyFlight::new(3, StepLength::Mantegna { alpha: 1.5, scale: 0.01 }).unwrap();
+        let n = 20000;
+        let mut mean = [0.0; 3];
+        let mut second = 0.0;
+        for _ in 0..n {
+            let u = flight.sample_direction(&mut rng);
+            assert!(close(u.iter().map(|x| x * x).sum::<f64>(), 1.0, 1e-12));
+            for (m, x) in mean.iter_mut().zip(u.iter()) {
+                *m += x / n as f64;
+            }
+            second += u[2] * u[2] / n as f64;
+        }
+        assert!(mean.iter().all(|m| m.abs() < 0.02));
+        // Each coordinate of a uniform point on the sphere in d dimensions has second moment 1/d
+        assert!(close(second, 1.0 / 3.0, 0.01));
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let flight = LevyFlight::new(2, StepLength::Stable { alpha: 1.5, sigma: 1.0 }).unwrap();
+        let positions = flight.sample_trajectory(&[1.0, -1.0], 50, &mut rng).unwrap();
+        assert_eq!(positions.len(), 51);
+        assert_eq!(positions[0], vec![1.0, -1.0]);
+        assert!(matches!(flight.sample_trajectory(&[0.0], 5, &mut rng), Err(Error::LengthMismatchError { expected: 2, found: 1 })));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(matches!(LevyFlight::new(0, StepLength::Stable { alpha: 1.5, sigma: 1.0 }), Err(Error::DimensionError { .. })));
+        assert!(matches!(LevyFlight::new(2, StepLength::Mantegna { alpha: 2.0, scale: 1.0 }), Err(Error::AlphaError { .. })));
+        assert!(matches!(LevyFlight::new(2, StepLength::Pareto { alpha: 1.0, l_min: 0.0 }), Err(Error::ScaleError { .. })));
+    }
+}
@@ -8,10 +8,38 @@
 //! - Standard form: S_alpha(sigma, beta, mu) - equivalent to the 'first parameterization' of Wikipedia (<https://en.wikipedia.org/wiki/Stable_distribution>) with c = sigma.
 //! - Nolan's form: S^0_alpha(sigma, beta, mu_0) - equivalent to the 'second parameterization' in Wikipedia with delta = mu_0 and gamma = sigma.
 pub mod alpha_stable;
+pub mod discrete_stable;
 pub mod error;
+pub mod fit;
+pub mod gclt;
+pub mod geometric_stable;
+pub mod gof;
 pub mod integrator;
+pub mod levy_flight;
+pub mod mcmc;
+pub mod mixture;
+pub mod process;
+pub mod random_field;
+pub mod regression;
+pub mod state_space;
+pub mod tail;
+pub mod tempered_stable;
+pub mod timeseries;
+pub mod truncated_stable;
 mod bisect;
+mod fft;
+mod fisher;
+mod linalg;
 mod numerical_result;
+mod optimize;
+mod special;
 
-pub use self::alpha_stable::{AlphaStable, Tol};
-pub use self::integrator::Integrator;
\ No newline at end of file
+pub use self::alpha_stable::{AlphaStable, Form, Tol};
+pub use self::discrete_stable::DiscreteStable;
+pub use self::geometric_stable::{Linnik, MittagLeffler};
+pub use self::integrator::Integrator;
+pub use self::levy_flight::LevyFlight;
+pub use self::mixture::StableMixture;
+pub use self::process::{LevyStableMotion, LfsmGenerator, Path, StableOU};
+pub use self::tempered_stable::TemperedStable;
+pub use self::truncated_stable::TruncatedStable;
\ No newline at end of file
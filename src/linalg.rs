@@ -0,0 +1,99 @@
+// Solves the linear system a x = b by Gaussian elimination with partial pivoting. Returns None if a is singular.
+pub(crate) fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+
+    let n = b.len();
+    let scale = a.iter().flatten().map(|x| x.abs()).fold(0.0, f64::max);
+    for k in 0..n {
+        let pivot = (k..n).max_by(|i, j| a[*i][k].abs().total_cmp(&a[*j][k].abs()))?;
+        if a[pivot][k].abs() <= n as f64 * f64::EPSILON * scale {
+            return None;
+        }
+        a.swap(k, pivot);
+        b.swap(k, pivot);
+        let (upper, lower) = a.split_at_mut(k + 1);
+        let row_k = &upper[k];
+        for (i, row_i) in lower.iter_mut().enumerate() {
+            let factor = row_i[k] / row_k[k];
+            for (x, y) in row_i[k..].iter_mut().zip(row_k[k..].iter()) {
+                *x -= factor * y;
+            }
+            b[k + 1 + i] -= factor * b[k];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        let s: f64 = (k + 1..n).map(|j| a[k][j] * x[j]).sum();
+        x[k] = (b[k] - s) / a[k][k];
+    }
+    Some(x)
+}
+
+// Least squares solution of x c = y from the normal equations, where each row of x is an observation.
+pub(crate) fn least_squares(x: &[Vec<f64>], y: &[f64]) -> Option<Vec<f64>> {
+
+    let m = x.first()?.len();
+    let mut xtx = vec![vec![0.0; m]; m];
+    let mut xty = vec![0.0; m];
+    for (row, y_i) in x.iter().zip(y.iter()) {
+        for i in 0..m {
+            xty[i] += row[i] * y_i;
+            for j in 0..m {
+                xtx[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    solve(xtx, xty)
+}
+
+// Inverse of a square matrix, column by column. Returns None if the matrix is singular.
+pub(crate) fn invert<const N: usize>(a: &[[f64; N]; N]) -> Option<[[f64; N]; N]> {
+    let mut inverse = [[0.0; N]; N];
+    for j in 0..N {
+        let mut e = vec![0.0; N];
+        e[j] = 1.0;
+        let column = solve(a.iter().map(|row| row.to_vec()).collect(), e)?;
+        for (row, c) in inverse.iter_mut().zip(column) {
+            row[j] = c;
+        }
+    }
+    Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{invert, least_squares, solve};
+    use crate::alpha_stable::close;
+
+    #[test]
+    fn test_solve() {
+        let a = vec![vec![0.0, 2.0, 1.0], vec![1.0, 1.0, 0.0], vec![3.0, 0.0, 1.0]];
+        let x = solve(a, vec![5.0, 3.0, 4.0]).unwrap();
+        for (x_i, expected) in x.iter().zip([1.0, 2.0, 1.0]) {
+            assert!(close(*x_i, expected, 1e-14));
+        }
+        assert!(solve(vec![vec![1.0, 2.0], vec![2.0, 4.0]], vec![1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn test_least_squares() {
+        // Exact fit of y = 1 + 2 t
+        let x: Vec<Vec<f64>> = (0..5).map(|t| vec![1.0, t as f64]).collect();
+        let y: Vec<f64> = (0..5).map(|t| 1.0 + 2.0 * t as f64).collect();
+        let c = least_squares(&x, &y).unwrap();
+        assert!(close(c[0], 1.0, 1e-12) && close(c[1], 2.0, 1e-12));
+    }
+
+    #[test]
+    fn test_invert() {
+        let a = [[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]];
+        let inverse = invert(&a).unwrap();
+        for (i, row) in a.iter().enumerate() {
+            for j in 0..3 {
+                let product: f64 = row.iter().zip(inverse.iter()).map(|(a_ik, inverse_k)| a_ik * inverse_k[j]).sum();
+                assert!(close(product, if i == j { 1.0 } else { 0.0 }, 1e-14));
+            }
+        }
+        assert!(invert(&[[1.0, 2.0], [2.0, 4.0]]).is_none());
+    }
+}
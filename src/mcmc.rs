@@ -0,0 +1,314 @@
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+use crate::fit::{initial_estimate, negative_log_likelihood, sample_quantile, MIN_SAMPLE_SIZE};
+
+/// Draw from the posterior distribution of the parameters (alpha, beta, sigma, mu) of an alpha stable distribution given
+/// data, by Metropolis-within-Gibbs sampling using the pdf of the distribution.
+///
+/// log_prior is the logarithm of the prior density of (alpha, beta, sigma, mu), up to a constant, and may be negative
+/// infinity to exclude parameters. Alpha is in any case restricted to [0.1, 2].
+///
+/// Each parameter is updated in turn by a Gaussian random walk on alpha, beta, ln sigma and mu_0 of Nolan's form, in which
+/// the likelihood varies continuously in alpha. Since mu differs from mu_0 by a function of the other parameters, the
+/// prior density is unchanged by the change of variable from mu to mu_0. Chains start from an empirical characteristic
+/// function estimate, all but the first perturbed so that their agreement is a check on convergence. During burn_in the
+/// random walk steps are adapted towards an acceptance rate of 0.44, after which they are fixed and n_samples draws are
+/// kept from each chain.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use alpha_stable::{mcmc::sample_posterior, AlphaStable};
+///
+/// let mut rng = thread_rng();
+/// let distribution = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+/// let data: Vec<f64> = (0..20).map(|_| distribution.sample(&mut rng)).collect();
+///
+/// // Uniform prior on alpha in [1, 2] and flat priors on beta, ln sigma and mu
+/// let log_prior = |p: &[f64; 4]| if p[0] >= 1.0 { -p[2].ln() } else { f64::NEG_INFINITY };
+/// let posterior = sample_posterior(&data, &log_prior, 2, 20, 20, &mut rng).unwrap();
+/// let alpha = posterior.summary(0.95).unwrap()[0];
+/// let (lower, upper) = alpha.get_interval();
+/// ```
+pub fn sample_posterior<R: Rng + ?Sized>(
+    data: &[f64],
+    log_prior: &dyn Fn(&[f64; 4]) -> f64,
+    n_chains: usize,
+    n_samples: usize,
+    burn_in: usize,
+    rng: &mut R,
+) -> Result<Posterior, Error> {
+
+    if data.len() < MIN_SAMPLE_SIZE {
+        return Err(Error::SampleSizeError { n: data.len() });
+    }
+    if n_chains == 0 {
+        return Err(Error::SampleSizeError { n: n_chains });
+    }
+    if n_samples < 2 {
+        return Err(Error::SampleSizeError { n: n_samples });
+    }
+
+    let estimate = initial_estimate(data)?;
+    let (alpha, beta, sigma, _, mu_0) = estimate.get_params();
+    let start = [alpha, beta, sigma.ln(), mu_0];
+    let (start_density, _) = log_posterior(&start, data, log_prior);
+    if start_density == f64::NEG_INFINITY {
+        return Err(Error::PosteriorSupportError);
+    }
+    let initial_steps = [0.1, 0.1, 0.1, 0.1 * sigma];
+
+    let mut chains = Vec::with_capacity(n_chains);
+    let mut acceptance_rates = Vec::with_capacity(n_chains);
+    for c in 0..n_chains {
+
+        // Perturbed starts that fall outside the support of the posterior are replaced by the estimate
+        let mut p = start;
+        if c > 0 {
+            for (p, step) in p.iter_mut().zip(initial_steps) {
+                *p += START_DISPERSION * step * rng.sample::<f64, _>(StandardNormal);
+            }
+        }
+        let (mut density, mut params) = log_posterior(&p, data, log_prior);
+        if density == f64::NEG_INFINITY {
+            p = start;
+            (density, params) = log_posterior(&p, data, log_prior);
+        }
+
+        let mut steps = initial_steps;
+        let mut accepted = [0; 4];
+        let mut batch_accepted = [0; 4];
+        let mut chain = Vec::with_capacity(n_samples);
+        for i in 0..burn_in + n_samples {
+            for k in 0..4 {
+                let mut proposal = p;
+                proposal[k] += steps[k] * rng.sample::<f64, _>(StandardNormal);
+                let (proposal_density, proposal_params) = log_posterior(&proposal, data, log_prior);
+                if rng.gen::<f64>().ln() < proposal_density - density {
+                    (p, density, params) = (proposal, proposal_density, proposal_params);
+                    batch_accepted[k] += 1;
+                    if i >= burn_in {
+                        accepted[k] += 1;
+                    }
+                }
+            }
+
+            // Steps grow or shrink by amounts that decay with the number of batches, towards the target acceptance rate
+            if i < burn_in && (i + 1) % ADAPTATION_BATCH == 0 {
+                let change = (1.0 / ((i + 1) / ADAPTATION_BATCH) as f64).sqrt().min(0.1);
+                for (step, n) in steps.iter_mut().zip(batch_accepted.iter_mut()) {
+                    let rate = *n as f64 / ADAPTATION_BATCH as f64;
+                    *step *= if rate > TARGET_ACCEPTANCE { change.exp() } else { (-change).exp() };
+                    *n = 0;
+                }
+            }
+            if i >= burn_in {
+                chain.push(params);
+            }
+        }
+
+        chains.push(chain);
+        acceptance_rates.push(accepted.map(|n| n as f64 / n_samples as f64));
+    }
+
+    Ok(Posterior { chains, acceptance_rates })
+}
+
+/// Draws from the posterior distribution of the parameters of an alpha stable distribution.
+#[derive(Debug, Clone)]
+pub struct Posterior {
+    chains: Vec<Vec<[f64; 4]>>,
+    acceptance_rates: Vec<[f64; 4]>,
+}
+
+impl Posterior {
+
+    /// Return the chains of draws of (alpha, beta, sigma, mu), after burn in.
+    pub fn get_chains(&self) -> &[Vec<[f64; 4]>] {
+        &self.chains
+    }
+
+    /// Return the proportion of proposals accepted after burn in, for alpha, beta, sigma and mu in each chain.
+    pub fn get_acceptance_rates(&self) -> &[[f64; 4]] {
+        &self.acceptance_rates
+    }
+
+    /// Summarise the draws of alpha, beta, sigma and mu, pooled over chains, with credible intervals at level.
+    pub fn summary(&self, level: f64) -> Result<[Summary; 4], Error> {
+
+        if level <= 0.0 || level >= 1.0 || level.is_nan() {
+            return Err(Error::ProbabilityError { p: level });
+        }
+
+        Ok([0, 1, 2, 3].map(|k| {
+            let mut sorted: Vec<f64> = self.chains.iter().flatten().map(|p| p[k]).collect();
+            sorted.sort_by(f64::total_cmp);
+            let (mean, variance) = mean_variance(&sorted);
+            Summary {
+                mean,
+                std_dev: variance.sqrt(),
+                median: sample_quantile(&sorted, 0.5),
+                interval: (sample_quantile(&sorted, 0.5 * (1.0 - level)), sample_quantile(&sorted, 0.5 * (1.0 + level))),
+                r_hat: self.r_hat(k),
+            }
+        }))
+    }
+
+    // Split R-hat: Gelman and Rubin's potential scale reduction factor with each chain split into halves, so that it also
+    // detects drift within a single chain.
+    fn r_hat(&self, k: usize) -> f64 {
+        let halves: Vec<Vec<f64>> = self.chains.iter()
+            .flat_map(|chain| {
+                let (first, second) = chain.split_at(chain.len() / 2);
+                [first, &second[second.len() - first.len()..]]
+            })
+            .map(|half| half.iter().map(|p| p[k]).collect())
+            .collect();
+
+        let n = halves[0].len() as f64;
+        let moments: Vec<(f64, f64)> = halves.iter().map(|half| mean_variance(half)).collect();
+        let within = moments.iter().map(|m| m.1).sum::<f64>() / moments.len() as f64;
+        let means: Vec<f64> = moments.iter().map(|m| m.0).collect();
+        let between = n * mean_variance(&means).1;
+        if within > 0.0 {
+            (((n - 1.0) * within + between) / (n * within)).sqrt()
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+/// Posterior summary of a parameter.
+#[derive(Debug, Clone, Copy)]
+pub struct Summary {
+    mean: f64,
+    std_dev: f64,
+    median: f64,
+    interval: (f64, f64),
+    r_hat: f64,
+}
+
+impl Summary {
+
+    /// Return the posterior mean.
+    pub fn get_mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Return the posterior standard deviation.
+    pub fn get_std_dev(&self) -> f64 {
+        self.std_dev
+    }
+
+    /// Return the posterior median.
+    pub fn get_median(&self) -> f64 {
+        self.median
+    }
+
+    /// Return the equal tailed credible interval as (lower, upper).
+    pub fn get_interval(&self) -> (f64, f64) {
+        self.interval
+    }
+
+    /// Return the split R-hat convergence diagnostic, which approaches 1 as the chains converge. NaN where the draws do
+    /// not vary.
+    pub fn get_r_hat(&self) -> f64 {
+        self.r_hat
+    }
+}
+
+// Acceptance rate targeted by the adaptation of random walk steps, optimal for updates of one parameter at a time.
+const TARGET_ACCEPTANCE: f64 = 0.44;
+
+// Number of iterations between adaptations of the random walk steps.
+const ADAPTATION_BATCH: usize = 10;
+
+// Number of initial random walk steps by which the starts of chains after the first are perturbed.
+const START_DISPERSION: f64 = 3.0;
+
+// Log posterior density, up to a constant, at the S0 parameters (alpha, beta, ln sigma, mu_0), with the standard form
+// parameters (alpha, beta, sigma, mu). The density of ln sigma carries the Jacobian sigma.
+fn log_posterior(p: &[f64; 4], data: &[f64], log_prior: &dyn Fn(&[f64; 4]) -> f64) -> (f64, [f64; 4]) {
+    let params = match AlphaStable::new_S0(p[0], p[1], p[2].exp(), p[3]) {
+        Ok(distribution) => {
+            let (alpha, beta, sigma, mu, _) = distribution.get_params();
+            [alpha, beta, sigma, mu]
+        },
+        Err(_) => return (f64::NEG_INFINITY, [f64::NAN; 4]),
+    };
+    let prior = log_prior(&params);
+    if prior.is_nan() || prior == f64::NEG_INFINITY {
+        return (f64::NEG_INFINITY, params);
+    }
+    (prior - negative_log_likelihood(p, data) + p[2], params)
+}
+
+fn mean_variance(x: &[f64]) -> (f64, f64) {
+    let n = x.len() as f64;
+    let mean = x.iter().sum::<f64>() / n;
+    (mean, x.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::sample_posterior;
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+
+    #[test]
+    fn test_posterior() {
+        let mut rng = StdRng::seed_from_u64(17);
+        let dist = AlphaStable::new(1.5, 0.5, 2.0, 1.0).unwrap();
+        let data: Vec<f64> = (0..40).map(|_| dist.sample(&mut rng)).collect();
+
+        let log_prior = |p: &[f64; 4]| -p[2].ln();
+        let posterior = sample_posterior(&data, &log_prior, 2, 200, 200, &mut rng).unwrap();
+        assert_eq!(posterior.get_chains().len(), 2);
+        assert_eq!(posterior.get_chains()[1].len(), 200);
+        for rate in posterior.get_acceptance_rates().iter().flatten() {
+            assert!(*rate > 0.15 && *rate < 0.8);
+        }
+
+        let summary = posterior.summary(0.95).unwrap();
+        for (s, value) in summary.iter().zip([1.5, 0.5, 2.0, 1.0]) {
+            let (lower, upper) = s.get_interval();
+            assert!(lower < value && value < upper);
+            assert!(lower < s.get_median() && s.get_median() < upper);
+            assert!(s.get_std_dev() > 0.0);
+            assert!(s.get_r_hat() < 1.2);
+        }
+    }
+
+    #[test]
+    fn test_prior() {
+        // A tight prior on alpha dominates the likelihood of a few observations
+        let mut rng = StdRng::seed_from_u64(18);
+        let dist = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        let data: Vec<f64> = (0..10).map(|_| dist.sample(&mut rng)).collect();
+
+        let log_prior = |p: &[f64; 4]| if (0.8..=0.9).contains(&p[0]) { 0.0 } else { f64::NEG_INFINITY };
+        let posterior = sample_posterior(&data, &log_prior, 1, 50, 50, &mut rng);
+        assert!(matches!(posterior, Err(Error::PosteriorSupportError)));
+
+        let log_prior = |p: &[f64; 4]| -0.5 * ((p[0] - 1.8) / 0.01).powi(2);
+        let summary = sample_posterior(&data, &log_prior, 1, 100, 100, &mut rng).unwrap().summary(0.9).unwrap();
+        assert!(close(summary[0].get_mean(), 1.8, 0.02));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let mut rng = StdRng::seed_from_u64(19);
+        let data = [1.0, -2.0, 0.5, 3.0, 0.1];
+        let log_prior = |_: &[f64; 4]| 0.0;
+        assert!(matches!(sample_posterior(&data[..4], &log_prior, 1, 10, 0, &mut rng), Err(Error::SampleSizeError { n: 4 })));
+        assert!(matches!(sample_posterior(&data, &log_prior, 0, 10, 0, &mut rng), Err(Error::SampleSizeError { n: 0 })));
+        let posterior = sample_posterior(&data, &log_prior, 1, 10, 0, &mut rng).unwrap();
+        assert!(matches!(posterior.summary(1.0), Err(Error::ProbabilityError { .. })));
+    }
+}
@@ -0,0 +1,255 @@
+use rand::Rng;
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+use crate::fit::fit_mle_weighted_from;
+
+/// Defines a finite mixture of alpha stable distributions, with density sum_k w_k f_k(x) for weights w_k summing to one.
+#[derive(Debug, Clone)]
+pub struct StableMixture {
+    weights: Vec<f64>,
+    components: Vec<AlphaStable>,
+}
+
+impl StableMixture {
+
+    /// Create mixture from weights and components. Weights must be non-negative, and are normalised to sum to one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::{AlphaStable, StableMixture};
+    ///
+    /// let calm = AlphaStable::new(1.9, 0.0, 0.5, 0.1).unwrap();
+    /// let turbulent = AlphaStable::new(1.4, -0.3, 2.0, -0.5).unwrap();
+    /// let mixture = StableMixture::new(vec![0.8, 0.2], vec![calm, turbulent]).unwrap();
+    /// let val = mixture.pdf(0.5).unwrap();
+    /// ```
+    pub fn new(weights: Vec<f64>, components: Vec<AlphaStable>) -> Result<StableMixture, Error> {
+
+        if components.is_empty() {
+            return Err(Error::DimensionError { dimension: 0 });
+        }
+        if weights.len() != components.len() {
+            return Err(Error::LengthMismatchError { expected: components.len(), found: weights.len() });
+        }
+        if let Some(weight) = weights.iter().find(|w| !(**w >= 0.0 && w.is_finite())) {
+            return Err(Error::WeightError { weight: *weight });
+        }
+        let total = weights.iter().sum::<f64>();
+        if total <= 0.0 {
+            return Err(Error::WeightError { weight: total });
+        }
+
+        let weights = weights.iter().map(|w| w / total).collect();
+        Ok(StableMixture { weights, components })
+    }
+
+    /// Return the weights of the components.
+    pub fn get_weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Return the components.
+    pub fn get_components(&self) -> &[AlphaStable] {
+        &self.components
+    }
+
+    /// Value of Probability Distribution function at x.
+    pub fn pdf(&self, x: f64) -> Result<f64, Error> {
+        let mut val = 0.0;
+        for (w, component) in self.weights.iter().zip(&self.components) {
+            val += w * component.pdf(x)?;
+        }
+        Ok(val)
+    }
+
+    /// Value of Cumulative Distribution function at x.
+    pub fn cdf(&self, x: f64) -> Result<f64, Error> {
+        let mut val = 0.0;
+        for (w, component) in self.weights.iter().zip(&self.components) {
+            val += w * component.cdf(x)?;
+        }
+        Ok(val.clamp(0.0, 1.0))
+    }
+
+    /// Sample from the distribution, by choosing a component with probability equal to its weight and sampling from it.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u = rng.gen::<f64>();
+        let mut cumulative = 0.0;
+        for (w, component) in self.weights.iter().zip(&self.components) {
+            cumulative += w;
+            if u < cumulative {
+                return component.sample(rng);
+            }
+        }
+        self.components[self.components.len() - 1].sample(rng)
+    }
+
+    /// Log likelihood of data under the mixture.
+    pub fn log_likelihood(&self, data: &[f64]) -> Result<f64, Error> {
+        let mut val = 0.0;
+        for x in data {
+            val += self.pdf(*x)?.ln();
+        }
+        Ok(val)
+    }
+
+    /// Fit a mixture with the same number of components to data by the EM algorithm, starting from this mixture.
+    ///
+    /// Each iteration computes the probability r_ik that observation i was drawn from component k, then takes the new
+    /// weight of component k as the mean of r_ik over i, and improves its parameters by a limited search for the weighted
+    /// maximum likelihood with weights r_ik, starting from its current parameters. As a generalised EM algorithm, which
+    /// only requires each step to increase the likelihood, this spares full searches while the responsibilities are still
+    /// changing. Iteration stops when the log likelihood increases by less than tol, or after max_iterations. A
+    /// component carrying less weight than one observation is left unchanged. Since a limited search can lower the
+    /// likelihood, the most likely mixture seen is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rand::thread_rng;
+    /// use alpha_stable::{AlphaStable, StableMixture};
+    ///
+    /// let mut rng = thread_rng();
+    /// let truth = StableMixture::new(
+    ///     vec![0.6, 0.4],
+    ///     vec![AlphaStable::new(1.8, 0.0, 1.0, -4.0).unwrap(), AlphaStable::new(1.5, 0.0, 0.5, 3.0).unwrap()],
+    /// ).unwrap();
+    /// let data: Vec<f64> = (0..500).map(|_| truth.sample(&mut rng)).collect();
+    ///
+    /// let start = StableMixture::new(
+    ///     vec![0.5, 0.5],
+    ///     vec![AlphaStable::new(1.5, 0.0, 1.0, -1.0).unwrap(), AlphaStable::new(1.5, 0.0, 1.0, 1.0).unwrap()],
+    /// ).unwrap();
+    /// let fitted = start.fit_em(&data, 1e-3, 50).unwrap();
+    /// ```
+    pub fn fit_em(&self, data: &[f64], tol: f64, max_iterations: usize) -> Result<StableMixture, Error> {
+
+        if data.is_empty() {
+            return Err(Error::SampleSizeError { n: 0 });
+        }
+
+        let n = data.len();
+        let mut mixture = self.clone();
+        let mut best = (f64::NEG_INFINITY, mixture.clone());
+        let mut previous = f64::NEG_INFINITY;
+        // The last pass only evaluates the mixture from the final M-step
+        for iteration in 0..=max_iterations {
+
+            // E-step: weighted densities of each component at each observation, normalised to probabilities
+            let mut responsibilities = vec![vec![0.0; n]; mixture.components.len()];
+            let mut log_likelihood = 0.0;
+            for (i, x) in data.iter().enumerate() {
+                let mut total = 0.0;
+                for (k, (w, component)) in mixture.weights.iter().zip(&mixture.components).enumerate() {
+                    responsibilities[k][i] = w * component.pdf(*x)?;
+                    total += responsibilities[k][i];
+                }
+                log_likelihood += total.ln();
+                for (r, w) in responsibilities.iter_mut().zip(&mixture.weights) {
+                    // An observation no component explains is shared by the weights
+                    r[i] = if total > 0.0 { r[i] / total } else { *w };
+                    // Negligible responsibilities are dropped, which spares their density evaluations in the M-step
+                    if r[i] < RESPONSIBILITY_FLOOR {
+                        r[i] = 0.0;
+                    }
+                }
+            }
+
+            if log_likelihood > best.0 {
+                best = (log_likelihood, mixture.clone());
+            }
+            if log_likelihood - previous < tol || iteration == max_iterations {
+                break;
+            }
+            previous = log_likelihood;
+
+            // M-step
+            for (k, r) in responsibilities.iter().enumerate() {
+                let total = r.iter().sum::<f64>();
+                mixture.weights[k] = total / n as f64;
+                if total >= 1.0 {
+                    mixture.components[k] = fit_mle_weighted_from(data, r, &mixture.components[k], M_STEP_ITERATIONS)?;
+                }
+            }
+            let total = mixture.weights.iter().sum::<f64>();
+            mixture.weights.iter_mut().for_each(|w| *w /= total);
+        }
+
+        Ok(best.1)
+    }
+}
+
+// Maximum number of Nelder-Mead iterations in each M-step.
+const M_STEP_ITERATIONS: usize = 40;
+
+// Responsibilities below this are set to zero.
+const RESPONSIBILITY_FLOOR: f64 = 1e-8;
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::StableMixture;
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+
+    fn two_regimes() -> StableMixture {
+        StableMixture::new(
+            vec![3.0, 2.0],
+            vec![AlphaStable::new(1.8, 0.0, 1.0, -4.0).unwrap(), AlphaStable::new(1.5, 0.5, 0.5, 3.0).unwrap()],
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_mixture() {
+        let mixture = two_regimes();
+        assert_eq!(mixture.get_weights(), &[0.6, 0.4]);
+        let x = 0.7;
+        let components = mixture.get_components();
+        let pdf = 0.6 * components[0].pdf(x).unwrap() + 0.4 * components[1].pdf(x).unwrap();
+        assert!(close(mixture.pdf(x).unwrap(), pdf, 1e-15));
+        let cdf = 0.6 * components[0].cdf(x).unwrap() + 0.4 * components[1].cdf(x).unwrap();
+        assert!(close(mixture.cdf(x).unwrap(), cdf, 1e-15));
+
+        let mut rng = StdRng::seed_from_u64(23);
+        let n = 20000;
+        let below = (0..n).filter(|_| mixture.sample(&mut rng) <= x).count() as f64 / n as f64;
+        assert!(close(below, cdf, 0.01));
+    }
+
+    #[test]
+    fn test_fit_em() {
+        let mut rng = StdRng::seed_from_u64(24);
+        let truth = two_regimes();
+        let data: Vec<f64> = (0..150).map(|_| truth.sample(&mut rng)).collect();
+
+        let start = StableMixture::new(
+            vec![0.5, 0.5],
+            vec![AlphaStable::new(1.5, 0.0, 1.0, -2.0).unwrap(), AlphaStable::new(1.5, 0.0, 1.0, 2.0).unwrap()],
+        ).unwrap();
+        let fitted = start.fit_em(&data, 0.1, 10).unwrap();
+        assert!(fitted.log_likelihood(&data).unwrap() > start.log_likelihood(&data).unwrap());
+        // The best mixture seen is kept, so more iterations never lower the likelihood
+        let shorter = start.fit_em(&data, 0.1, 3).unwrap();
+        assert!(fitted.log_likelihood(&data).unwrap() >= shorter.log_likelihood(&data).unwrap());
+        assert_eq!(start.fit_em(&data, 0.1, 0).unwrap().get_weights(), start.get_weights());
+        assert!(close(fitted.get_weights()[0], 0.6, 0.06));
+        for (component, truth) in fitted.get_components().iter().zip(truth.get_components()) {
+            let (_, _, fitted_sigma, _, fitted_mu_0) = component.get_params();
+            let (_, _, sigma, _, mu_0) = truth.get_params();
+            assert!(close(fitted_sigma, sigma, 0.3 * sigma));
+            assert!(close(fitted_mu_0, mu_0, 0.5));
+        }
+    }
+
+    #[test]
+    fn test_invalid_mixture() {
+        let component = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        assert!(matches!(StableMixture::new(vec![], vec![]), Err(Error::DimensionError { dimension: 0 })));
+        assert!(matches!(StableMixture::new(vec![1.0], vec![component.clone(), component.clone()]), Err(Error::LengthMismatchError { .. })));
+        assert!(matches!(StableMixture::new(vec![-1.0], vec![component.clone()]), Err(Error::WeightError { .. })));
+        assert!(matches!(StableMixture::new(vec![0.0], vec![component]), Err(Error::WeightError { .. })));
+    }
+}
@@ -18,7 +18,7 @@ where
 
     #[allow(dead_code)]
     pub(crate) fn has_err(&self) -> bool {
-        !self.error.is_none() 
+        self.error.is_some() 
     }
 
     pub(crate) fn estimate(&self) -> Result<f64, Error> {
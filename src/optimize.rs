@@ -0,0 +1,90 @@
+// Minimises f with the Nelder-Mead simplex method. The initial simplex is x0 together with x0 displaced by steps[i]
+// along each axis i. Points where f is not finite are treated as infeasible. Stops when the spread of function values
+// over the simplex falls below tol, or after max_iter iterations. Returns the best point found and its value.
+pub(crate) fn nelder_mead(f: &dyn Fn(&[f64]) -> f64, x0: &[f64], steps: &[f64], tol: f64, max_iter: usize) -> (Vec<f64>, f64) {
+
+    let n = x0.len();
+    let eval = |x: &[f64]| {
+        let val = f(x);
+        if val.is_nan() { f64::INFINITY } else { val }
+    };
+
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(n + 1);
+    simplex.push((x0.to_vec(), eval(x0)));
+    for i in 0..n {
+        let mut x = x0.to_vec();
+        x[i] += steps[i];
+        let val = eval(&x);
+        simplex.push((x, val));
+    }
+
+    for _ in 0..max_iter {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let (best, worst) = (simplex[0].1, simplex[n].1);
+        if best.is_finite() && worst - best <= tol {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..n).map(|j| simplex[..n].iter().map(|p| p.0[j]).sum::<f64>() / n as f64).collect();
+        let towards = |t: f64| -> Vec<f64> {
+            centroid.iter().zip(simplex[n].0.iter()).map(|(c, w)| c + t * (w - c)).collect()
+        };
+
+        let reflected = towards(-1.0);
+        let f_reflected = eval(&reflected);
+
+        if f_reflected < simplex[0].1 {
+            let expanded = towards(-2.0);
+            let f_expanded = eval(&expanded);
+            simplex[n] = if f_expanded < f_reflected { (expanded, f_expanded) } else { (reflected, f_reflected) };
+            continue;
+        }
+
+        if f_reflected < simplex[n - 1].1 {
+            simplex[n] = (reflected, f_reflected);
+            continue;
+        }
+
+        let contracted = if f_reflected < simplex[n].1 { towards(-0.5) } else { towards(0.5) };
+        let f_contracted = eval(&contracted);
+        if f_contracted < simplex[n].1.min(f_reflected) {
+            simplex[n] = (contracted, f_contracted);
+            continue;
+        }
+
+        // Shrink towards the best point
+        let x_best = simplex[0].0.clone();
+        for p in simplex.iter_mut().skip(1) {
+            for (x, b) in p.0.iter_mut().zip(x_best.iter()) {
+                *x = b + 0.5 * (*x - b);
+            }
+            p.1 = eval(&p.0);
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex.swap_remove(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::nelder_mead;
+    use crate::alpha_stable::close;
+
+    #[test]
+    fn test_rosenbrock() {
+        let f = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let (x, val) = nelder_mead(&f, &[-1.2, 1.0], &[0.5, 0.5], 1e-14, 5000);
+        assert!(close(x[0], 1.0, 1e-4) && close(x[1], 1.0, 1e-4));
+        assert!(val < 1e-8);
+    }
+
+    #[test]
+    fn test_infeasible_region() {
+        // Minimum of the unconstrained quadratic lies outside the feasible region x >= 1
+        let f = |x: &[f64]| if x[0] < 1.0 { f64::INFINITY } else { x[0] * x[0] };
+        let (x, _) = nelder_mead(&f, &[3.0], &[1.0], 1e-12, 1000);
+        assert!(close(x[0], 1.0, 1e-5));
+    }
+}
@@ -0,0 +1,427 @@
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+use crate::alpha_stable::{close, AlphaStable, Tol};
+use crate::error::Error;
+use crate::fft::convolve;
+use crate::fit::fit_mle;
+
+/// Sample path of a stochastic process observed on a time grid.
+#[derive(Debug, Clone)]
+pub struct Path {
+    times: Vec<f64>,
+    values: Vec<f64>,
+}
+
+impl Path {
+
+    /// Create path from observation times and values, which must have equal lengths.
+    pub fn new(times: Vec<f64>, values: Vec<f64>) -> Result<Path, Error> {
+        if times.len() != values.len() {
+            return Err(Error::LengthMismatchError { expected: times.len(), found: values.len() });
+        }
+        Ok(Path { times, values })
+    }
+
+    /// Return observation times.
+    pub fn get_times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Return values at the observation times.
+    pub fn get_values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Number of observations.
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// True if the path has no observations.
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Increments between successive observations.
+    pub fn increments(&self) -> Vec<f64> {
+        self.values.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+}
+
+/// Levy stable motion with drift: X(t) = drift t + L(t), where L has stationary independent increments with
+/// L(t + dt) - L(t) ~ S_alpha(sigma dt^(1/alpha), beta, 0) in Standard form.
+#[derive(Debug)]
+pub struct LevyStableMotion {
+    alpha: f64,
+    beta: f64,
+    sigma: f64,
+    drift: f64,
+}
+
+impl LevyStableMotion {
+
+    /// Create process whose unit time increments, before drift, are S_alpha(sigma, beta, 0).
+    pub fn new(alpha: f64, beta: f64, sigma: f64, drift: f64) -> Result<LevyStableMotion, Error> {
+        AlphaStable::new(alpha, beta, sigma, 0.0)?;
+        Ok(LevyStableMotion { alpha, beta, sigma, drift })
+    }
+
+    /// Return parameters as tuple of (alpha, beta, sigma, drift).
+    pub fn get_params(&self) -> (f64, f64, f64, f64) {
+        (self.alpha, self.beta, self.sigma, self.drift)
+    }
+
+    /// Distribution of the increment over a time step dt, excluding drift.
+    pub fn increment(&self, dt: f64) -> Result<AlphaStable, Error> {
+        AlphaStable::new(self.alpha, self.beta, self.sigma * dt.powf(1.0 / self.alpha), 0.0)
+    }
+
+    /// Sample a path starting from zero at times[0] on a strictly increasing, possibly non-uniform, time grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::LevyStableMotion;
+    ///
+    /// let mut rng = thread_rng();
+    /// let process = LevyStableMotion::new(1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let times: Vec<f64> = (0..=100).map(|k| 0.01 * k as f64).collect();
+    /// let path = process.sample_path(&times, &mut rng).unwrap();
+    /// ```
+    pub fn sample_path<R: Rng + ?Sized>(&self, times: &[f64], rng: &mut R) -> Result<Path, Error> {
+
+        check_time_grid(times)?;
+
+        let mut values = Vec::with_capacity(times.len());
+        let mut x = 0.0;
+        values.push(x);
+        for w in times.windows(2) {
+            let dt = w[1] - w[0];
+            x += self.increment(dt)?.sample(rng) + self.drift * dt;
+            values.push(x);
+        }
+
+        Path::new(times.to_vec(), values)
+    }
+
+    /// Sample n independent paths on the same time grid.
+    pub fn sample_paths<R: Rng + ?Sized>(&self, times: &[f64], n: usize, rng: &mut R) -> Result<Vec<Path>, Error> {
+        (0..n).map(|_| self.sample_path(times, rng)).collect()
+    }
+}
+
+/// Linear fractional stable motion with stability index alpha, Hurst index H and scale sigma, generated on the integer
+/// times 0, 1, ..., n with the FFT moving average algorithm of Stoev and Taqqu (2004).
+///
+/// The increments are approximated by Y(k) = sum_{j=1}^{mM} g(j/m) L(mk - j), where g(x) = x_+^(H - 1/alpha) - (x - 1)_+^(H - 1/alpha)
+/// and L are i.i.d. S_alpha(sigma m^(-1/alpha), 0, 0). The resolution m and kernel cut-off M control the accuracy.
+#[derive(Debug)]
+pub struct LfsmGenerator {
+    alpha: f64,
+    h: f64,
+    sigma: f64,
+    resolution: usize,
+    cutoff: usize,
+}
+
+impl LfsmGenerator {
+
+    /// Create generator. H must lie in (0,1).
+    pub fn new(alpha: f64, h: f64, sigma: f64) -> Result<LfsmGenerator, Error> {
+        AlphaStable::new(alpha, 0.0, sigma, 0.0)?;
+        if h <= 0.0 || h >= 1.0 || h.is_nan() {
+            return Err(Error::HurstError { h });
+        }
+        Ok(LfsmGenerator { alpha, h, sigma, resolution: 128, cutoff: 600 })
+    }
+
+    /// Set the resolution m (points per unit time) and kernel cut-off M (in units of time). Both are at least 1.
+    pub fn with_resolution(&mut self, resolution: usize, cutoff: usize) -> &mut Self {
+        self.resolution = resolution.max(1);
+        self.cutoff = cutoff.max(1);
+        self
+    }
+
+    /// Return parameters as tuple of (alpha, H, sigma).
+    pub fn get_params(&self) -> (f64, f64, f64) {
+        (self.alpha, self.h, self.sigma)
+    }
+
+    /// Sample a path at times 0, 1, ..., n starting from zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::LfsmGenerator;
+    ///
+    /// let mut rng = thread_rng();
+    /// let mut generator = LfsmGenerator::new(1.5, 0.8, 1.0).unwrap();
+    /// generator.with_resolution(16, 100);
+    /// let path = generator.sample_path(100, &mut rng).unwrap();
+    /// ```
+    pub fn sample_path<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Result<Path, Error> {
+
+        let m = self.resolution;
+        let d = self.h - 1.0 / self.alpha;
+
+        let kernel: Vec<f64> = (1..=m * self.cutoff).map(|j| {
+            let x = j as f64 / m as f64;
+            let tail = if x > 1.0 { (x - 1.0).powf(d) } else { 0.0 };
+            x.powf(d) - tail
+        }).collect();
+
+        let noise_distribution = AlphaStable::new(self.alpha, 0.0, self.sigma * (m as f64).powf(-1.0 / self.alpha), 0.0)?;
+        let noise: Vec<f64> = (0..m * (self.cutoff + n)).map(|_| noise_distribution.sample(rng)).collect();
+
+        // Y(k) sits at index mk + mM - 1 of the convolution
+        let conv = convolve(&kernel, &noise);
+
+        let mut values = Vec::with_capacity(n + 1);
+        let mut x = 0.0;
+        values.push(x);
+        for k in 0..n {
+            x += conv[m * (k + self.cutoff) - 1];
+            values.push(x);
+        }
+
+        Path::new((0..=n).map(|t| t as f64).collect(), values)
+    }
+}
+
+/// Ornstein-Uhlenbeck process driven by stable noise: dX(t) = -lambda (X(t) - theta) dt + dL(t), where L is Levy stable
+/// motion with unit time increments S_alpha(sigma, beta, 0) in Standard form.
+///
+/// Over a step dt the process has the exact transition X(t + dt) = theta + (X(t) - theta) exp(-lambda dt) + Z, where
+/// Z ~ S_alpha(sigma ((1 - exp(-alpha lambda dt)) / (alpha lambda))^(1/alpha), beta, mu_dt), with mu_dt = 0 for alpha != 1 and
+/// mu_dt = 2 beta sigma (1 - exp(-lambda dt) (1 + lambda dt)) / (pi lambda) for alpha == 1.
+#[derive(Debug)]
+pub struct StableOU {
+    lambda: f64,
+    theta: f64,
+    alpha: f64,
+    beta: f64,
+    sigma: f64,
+}
+
+impl StableOU {
+
+    /// Create process with mean reversion rate lambda > 0, long run level theta and driving noise parameters.
+    pub fn new(lambda: f64, theta: f64, alpha: f64, beta: f64, sigma: f64) -> Result<StableOU, Error> {
+        AlphaStable::new(alpha, beta, sigma, 0.0)?;
+        if lambda <= 0.0 || lambda.is_nan() {
+            return Err(Error::MeanReversionError { lambda });
+        }
+        Ok(StableOU { lambda, theta, alpha, beta, sigma })
+    }
+
+    /// Return parameters as tuple of (lambda, theta, alpha, beta, sigma).
+    pub fn get_params(&self) -> (f64, f64, f64, f64, f64) {
+        (self.lambda, self.theta, self.alpha, self.beta, self.sigma)
+    }
+
+    /// Distribution of the integrated noise Z over a time step dt.
+    pub fn noise(&self, dt: f64) -> Result<AlphaStable, Error> {
+        let (a, l) = (self.alpha, self.lambda);
+        let sigma = self.sigma * ((1.0 - (-a * l * dt).exp()) / (a * l)).powf(1.0 / a);
+        let mu = if close(a, 1.0, Tol::default().alpha) {
+            self.beta * self.sigma * (1.0 - (-l * dt).exp() * (1.0 + l * dt)) / (0.5 * PI * l)
+        } else {
+            0.0
+        };
+        AlphaStable::new(a, self.beta, sigma, mu)
+    }
+
+    /// Distribution of X(t + dt) given X(t) = x.
+    pub fn transition(&self, x: f64, dt: f64) -> Result<AlphaStable, Error> {
+        let noise = self.noise(dt)?;
+        let (alpha, beta, sigma, mu, _) = noise.get_params();
+        AlphaStable::new(alpha, beta, sigma, mu + self.theta + (x - self.theta) * (-self.lambda * dt).exp())
+    }
+
+    /// Sample a path starting from x_0 at times[0] on a strictly increasing, possibly non-uniform, time grid using the exact transition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::StableOU;
+    ///
+    /// let mut rng = thread_rng();
+    /// let process = StableOU::new(2.0, 1.0, 1.5, 0.0, 0.3).unwrap();
+    /// let times: Vec<f64> = (0..=100).map(|k| 0.01 * k as f64).collect();
+    /// let path = process.sample_path(0.0, &times, &mut rng).unwrap();
+    /// ```
+    pub fn sample_path<R: Rng + ?Sized>(&self, x_0: f64, times: &[f64], rng: &mut R) -> Result<Path, Error> {
+
+        check_time_grid(times)?;
+
+        let mut values = Vec::with_capacity(times.len());
+        let mut x = x_0;
+        values.push(x);
+        for w in times.windows(2) {
+            let dt = w[1] - w[0];
+            x = self.theta + (x - self.theta) * (-self.lambda * dt).exp() + self.noise(dt)?.sample(rng);
+            values.push(x);
+        }
+
+        Path::new(times.to_vec(), values)
+    }
+
+    /// Estimate the process from values observed at a fixed time step dt.
+    ///
+    /// The mean reversion rate and long run level are estimated by least squares regression of each value on its
+    /// predecessor, which is consistent under infinite variance noise. The noise parameters are then estimated by
+    /// maximum likelihood from the regression residuals, see [`fit_mle`].
+    pub fn fit(values: &[f64], dt: f64) -> Result<StableOU, Error> {
+
+        let n = values.len().saturating_sub(1);
+        if n < 2 {
+            return Err(Error::SampleSizeError { n: values.len() });
+        }
+
+        let (x, y) = (&values[..n], &values[1..]);
+        let x_mean = x.iter().sum::<f64>() / n as f64;
+        let y_mean = y.iter().sum::<f64>() / n as f64;
+        let sxy: f64 = x.iter().zip(y.iter()).map(|(a, b)| (a - x_mean) * (b - y_mean)).sum();
+        let sxx: f64 = x.iter().map(|a| (a - x_mean).powi(2)).sum();
+        let slope = sxy / sxx;
+        let intercept = y_mean - slope * x_mean;
+
+        let lambda = -slope.ln() / dt;
+        if !(lambda > 0.0 && lambda.is_finite()) {
+            return Err(Error::MeanReversionError { lambda });
+        }
+
+        let residuals: Vec<f64> = x.iter().zip(y.iter()).map(|(a, b)| b - slope * a - intercept).collect();
+        let (alpha, beta, sigma_dt, mu_dt, _) = fit_mle(&residuals)?.get_params();
+
+        let sigma = sigma_dt * (alpha * lambda / (1.0 - (-alpha * lambda * dt).exp())).powf(1.0 / alpha);
+
+        // The regression intercept plus the residual location is theta (1 - slope) plus the location of the noise
+        let mut process = StableOU::new(lambda, 0.0, alpha, beta, sigma)?;
+        let (_, _, _, noise_mu, _) = process.noise(dt)?.get_params();
+        process.theta = (intercept + mu_dt - noise_mu) / (1.0 - slope);
+        Ok(process)
+    }
+}
+
+// Checks that a time grid is non-empty and strictly increasing.
+pub(crate) fn check_time_grid(times: &[f64]) -> Result<(), Error> {
+    if times.is_empty() {
+        return Err(Error::TimeGridError { index: 0 });
+    }
+    for (i, w) in times.windows(2).enumerate() {
+        if w[1].partial_cmp(&w[0]) != Some(Ordering::Greater) {
+            return Err(Error::TimeGridError { index: i + 1 });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{LevyStableMotion, LfsmGenerator, StableOU};
+    use crate::alpha_stable::close;
+    use crate::error::Error;
+
+    #[test]
+    fn test_time_grid_errors() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let process = LevyStableMotion::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        assert!(matches!(process.sample_path(&[], &mut rng), Err(Error::TimeGridError { index: 0 })));
+        assert!(matches!(process.sample_path(&[0.0, 1.0, 1.0], &mut rng), Err(Error::TimeGridError { index: 2 })));
+    }
+
+    #[test]
+    fn test_brownian_scaling_on_non_uniform_grid() {
+        // alpha == 2 gives Brownian motion with variance 2 sigma^2 t
+        let mut rng = StdRng::seed_from_u64(2);
+        let (sigma, drift) = (0.5, 1.0);
+        let process = LevyStableMotion::new(2.0, 0.0, sigma, drift).unwrap();
+        let times = [0.0, 0.01, 0.1, 0.5, 0.55, 2.0];
+        let paths = process.sample_paths(&times, 4000, &mut rng).unwrap();
+        let ends: Vec<f64> = paths.iter().map(|p| p.get_values()[times.len() - 1]).collect();
+        let mean = ends.iter().sum::<f64>() / ends.len() as f64;
+        let var = ends.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / ends.len() as f64;
+        assert!(close(mean, drift * 2.0, 0.05));
+        assert!(close(var, 2.0 * sigma * sigma * 2.0, 0.1));
+        assert_eq!(paths[0].increments().len(), times.len() - 1);
+    }
+
+    #[test]
+    fn test_hurst_error() {
+        for h in [0.0, 1.0, -0.5] {
+            assert!(matches!(LfsmGenerator::new(1.5, h, 1.0), Err(Error::HurstError { .. })));
+        }
+    }
+
+    #[test]
+    fn test_lfsm_self_similarity() {
+        // alpha == 2 gives fractional Brownian motion with Var X(t) proportional to t^(2H)
+        let mut rng = StdRng::seed_from_u64(3);
+        let h = 0.8;
+        let mut generator = LfsmGenerator::new(2.0, h, 1.0).unwrap();
+        generator.with_resolution(8, 100);
+        let n_paths = 1000;
+        let (mut var_4, mut var_16) = (0.0, 0.0);
+        for _ in 0..n_paths {
+            let path = generator.sample_path(16, &mut rng).unwrap();
+            var_4 += path.get_values()[4].powi(2) / n_paths as f64;
+            var_16 += path.get_values()[16].powi(2) / n_paths as f64;
+        }
+        let h_estimate = 0.5 * (var_16 / var_4).ln() / 4.0_f64.ln();
+        assert!(close(h_estimate, h, 0.05));
+    }
+
+    #[test]
+    fn test_ou_transition() {
+        // alpha == 2 has Gaussian transitions with variance sigma^2 (1 - exp(-2 lambda dt)) / lambda
+        let (lambda, theta, sigma, dt) = (1.5, 2.0, 0.4, 0.3);
+        let process = StableOU::new(lambda, theta, 2.0, 0.0, sigma).unwrap();
+        let (_, _, scale, mu, _) = process.transition(1.0, dt).unwrap().get_params();
+        assert!(close(mu, theta + (1.0 - theta) * (-lambda * dt).exp(), 1e-14));
+        assert!(close(2.0 * scale * scale, sigma * sigma * (1.0 - (-2.0 * lambda * dt).exp()) / lambda, 1e-14));
+
+        // Aggregating two half steps of the noise gives one full step when alpha == 1
+        let process = StableOU::new(lambda, theta, 1.0, 0.7, sigma).unwrap();
+        let (_, _, s_half, mu_half, _) = process.noise(0.5 * dt).unwrap().get_params();
+        let (_, _, s_full, mu_full, _) = process.noise(dt).unwrap().get_params();
+        let decay = (-lambda * 0.5 * dt).exp();
+        // decay Z_1 + Z_2: scales add and scaling by decay adds -2 beta sigma decay ln(decay) / pi to the location
+        assert!(close(s_half * (1.0 + decay), s_full, 1e-14));
+        let mu_sum = mu_half * (1.0 + decay) - 2.0 * 0.7 * s_half * decay * decay.ln() / PI;
+        assert!(close(mu_sum, mu_full, 1e-14));
+    }
+
+    #[test]
+    fn test_ou_fit() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let dt = 0.25;
+        let process = StableOU::new(1.0, 1.0, 1.6, 0.0, 0.5).unwrap();
+        let times: Vec<f64> = (0..=600).map(|k| dt * k as f64).collect();
+        let path = process.sample_path(1.0, &times, &mut rng).unwrap();
+        let fitted = StableOU::fit(path.get_values(), dt).unwrap();
+        let (lambda, theta, alpha, _, sigma) = fitted.get_params();
+        assert!(close(lambda, 1.0, 0.3));
+        assert!(close(theta, 1.0, 0.15));
+        assert!(close(alpha, 1.6, 0.25));
+        assert!(close(sigma, 0.5, 0.1));
+    }
+
+    #[test]
+    fn test_mean_reversion_error() {
+        assert!(matches!(StableOU::new(0.0, 0.0, 1.5, 0.0, 1.0), Err(Error::MeanReversionError { .. })));
+        // A random walk has no mean reversion
+        let values: Vec<f64> = (0..20).map(|k| k as f64).collect();
+        assert!(StableOU::fit(&values, 1.0).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+use crate::fft::convolve_2d;
+
+/// Stationary stable moving average random field on a two dimensional grid: X(i, j) = sum_{k,l} K(k, l) L(i - k, j - l),
+/// with L i.i.d. from the noise distribution and the kernel K given as rows.
+///
+/// Each value is distributed as the sum of the scaled noise K(k, l) L, which is alpha stable with
+/// sigma^alpha = sigma_L^alpha sum_{k,l} |K(k, l)|^alpha, and correlation between values extends over the size of the kernel.
+/// Fields are generated by FFT convolution of the kernel with a grid of noise.
+#[derive(Debug, Clone)]
+pub struct MovingAverageField {
+    kernel: Vec<Vec<f64>>,
+    noise: AlphaStable,
+}
+
+impl MovingAverageField {
+
+    /// Create field from kernel and noise distribution. The kernel must be rectangular, and not all zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::{random_field::{radial_kernel, MovingAverageField}, AlphaStable};
+    ///
+    /// let kernel = radial_kernel(&|r| (-r / 3.0).exp(), 8);
+    /// let noise = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let field = MovingAverageField::new(kernel, noise).unwrap();
+    /// ```
+    pub fn new(kernel: Vec<Vec<f64>>, noise: AlphaStable) -> Result<MovingAverageField, Error> {
+        check_kernel(&kernel)?;
+        Ok(MovingAverageField { kernel, noise })
+    }
+
+    /// Return kernel.
+    pub fn get_kernel(&self) -> &[Vec<f64>] {
+        &self.kernel
+    }
+
+    /// Return noise distribution.
+    pub fn get_noise(&self) -> &AlphaStable {
+        &self.noise
+    }
+
+    /// Distribution of each value of the field.
+    pub fn marginal(&self) -> Result<AlphaStable, Error> {
+        let mut marginal: Option<AlphaStable> = None;
+        for k in self.kernel.iter().flatten().filter(|k| **k != 0.0) {
+            let term = self.noise.scale(*k)?;
+            marginal = Some(match marginal {
+                Some(marginal) => marginal.convolve(&term)?,
+                None => term,
+            });
+        }
+        // The kernel is checked to have a non-zero entry, so the error is not raised
+        marginal.ok_or(Error::ScaleError { scale: 0.0 })
+    }
+
+    /// Sample the field on an n_rows by n_cols grid, returned as rows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::{random_field::{radial_kernel, MovingAverageField}, AlphaStable};
+    ///
+    /// let mut rng = thread_rng();
+    /// let kernel = radial_kernel(&|r| (-r / 3.0).exp(), 8);
+    /// let noise = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let field = MovingAverageField::new(kernel, noise).unwrap().sample(64, 64, &mut rng);
+    /// assert_eq!((field.len(), field[0].len()), (64, 64));
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, n_rows: usize, n_cols: usize, rng: &mut R) -> Vec<Vec<f64>> {
+        moving_average(&self.kernel, n_rows, n_cols, &mut || self.noise.sample(rng))
+    }
+}
+
+/// Sub-Gaussian stable random field on a two dimensional grid: X = A^(1/2) G, where G is a stationary Gaussian moving
+/// average field, G(i, j) = sum_{k,l} K(k, l) W(i - k, j - l) with W i.i.d. N(0, 1), and A is a single positive stable
+/// variable S_(alpha/2)((cos(pi alpha / 4))^(2/alpha), 1, 0) for the whole field.
+///
+/// The field is symmetric alpha stable with the correlation structure of G: any linear combination sum c_ij X(i, j) is
+/// S_alpha(s / sqrt(2), 0, 0), where s^2 is the variance of sum c_ij G(i, j). Unlike a moving average field, large values
+/// are not isolated, since A scales the whole field.
+#[derive(Debug, Clone)]
+pub struct SubGaussianField {
+    alpha: f64,
+    kernel: Vec<Vec<f64>>,
+    mixing: AlphaStable,
+}
+
+impl SubGaussianField {
+
+    /// Create field from alpha in (0, 2) and the kernel of the Gaussian field. The kernel must be rectangular, and not
+    /// all zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::random_field::{radial_kernel, SubGaussianField};
+    ///
+    /// let kernel = radial_kernel(&|r| (-0.5 * (r / 3.0).powi(2)).exp(), 8);
+    /// let field = SubGaussianField::new(1.5, kernel).unwrap();
+    /// ```
+    pub fn new(alpha: f64, kernel: Vec<Vec<f64>>) -> Result<SubGaussianField, Error> {
+        if alpha <= 0.0 || alpha >= 2.0 || alpha.is_nan() {
+            return Err(Error::AlphaError { alpha });
+        }
+        check_kernel(&kernel)?;
+        let mixing = AlphaStable::new(0.5 * alpha, 1.0, (0.25 * PI * alpha).cos().powf(2.0 / alpha), 0.0)?;
+        Ok(SubGaussianField { alpha, kernel, mixing })
+    }
+
+    /// Return alpha.
+    pub fn get_alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Return kernel of the Gaussian field.
+    pub fn get_kernel(&self) -> &[Vec<f64>] {
+        &self.kernel
+    }
+
+    /// Return distribution of the positive stable variable A.
+    pub fn get_mixing_distribution(&self) -> &AlphaStable {
+        &self.mixing
+    }
+
+    /// Distribution of each value of the field, S_alpha(s / sqrt(2), 0, 0) with s^2 = sum_{k,l} K(k, l)^2.
+    pub fn marginal(&self) -> Result<AlphaStable, Error> {
+        let variance = self.kernel.iter().flatten().map(|k| k * k).sum::<f64>();
+        AlphaStable::new(self.alpha, 0.0, (0.5 * variance).sqrt(), 0.0)
+    }
+
+    /// Sample the field on an n_rows by n_cols grid, returned as rows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::random_field::{radial_kernel, SubGaussianField};
+    ///
+    /// let mut rng = thread_rng();
+    /// let kernel = radial_kernel(&|r| (-0.5 * (r / 3.0).powi(2)).exp(), 8);
+    /// let field = SubGaussianField::new(1.5, kernel).unwrap().sample(64, 64, &mut rng);
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, n_rows: usize, n_cols: usize, rng: &mut R) -> Vec<Vec<f64>> {
+        let mut field = moving_average(&self.kernel, n_rows, n_cols, &mut || StandardNormal.sample(rng));
+        let a = self.mixing.sample(rng).max(0.0).sqrt();
+        field.iter_mut().flatten().for_each(|x| *x *= a);
+        field
+    }
+}
+
+/// Square kernel of side 2 radius + 1 with K(k, l) = f(r), where r is the distance of (k, l) from the centre.
+///
+/// # Example
+///
+/// ```
+/// use alpha_stable::random_field::radial_kernel;
+///
+/// let kernel = radial_kernel(&|r| 1.0 / (1.0 + r * r), 2);
+/// assert_eq!(kernel.len(), 5);
+/// assert_eq!(kernel[2][2], 1.0);
+/// assert_eq!(kernel[2][3], 0.5);
+/// ```
+pub fn radial_kernel(f: &dyn Fn(f64) -> f64, radius: usize) -> Vec<Vec<f64>> {
+    let side = 2 * radius + 1;
+    (0..side).map(|k| (0..side).map(|l| {
+        let (dk, dl) = (k as f64 - radius as f64, l as f64 - radius as f64);
+        f(dk.hypot(dl))
+    }).collect()).collect()
+}
+
+// Checks that a kernel is rectangular, non-empty and not all zero.
+fn check_kernel(kernel: &[Vec<f64>]) -> Result<(), Error> {
+    let cols = kernel.first().map_or(0, |row| row.len());
+    if cols == 0 {
+        return Err(Error::DimensionError { dimension: 0 });
+    }
+    if let Some(row) = kernel.iter().find(|row| row.len() != cols) {
+        return Err(Error::LengthMismatchError { expected: cols, found: row.len() });
+    }
+    if kernel.iter().flatten().all(|k| *k == 0.0) {
+        return Err(Error::ScaleError { scale: 0.0 });
+    }
+    Ok(())
+}
+
+// Moving average of noise drawn row by row on the grid that the kernel covers, keeping the n_rows by n_cols values whose
+// sums include the whole kernel.
+fn moving_average(kernel: &[Vec<f64>], n_rows: usize, n_cols: usize, noise: &mut dyn FnMut() -> f64) -> Vec<Vec<f64>> {
+    if n_rows == 0 || n_cols == 0 {
+        return Vec::new();
+    }
+    let (k_rows, k_cols) = (kernel.len(), kernel[0].len());
+    let grid: Vec<Vec<f64>> = (0..n_rows + k_rows - 1).map(|_| (0..n_cols + k_cols - 1).map(|_| noise()).collect()).collect();
+    let conv = convolve_2d(kernel, &grid);
+    conv[k_rows - 1..k_rows - 1 + n_rows].iter().map(|row| row[k_cols - 1..k_cols - 1 + n_cols].to_vec()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{radial_kernel, MovingAverageField, SubGaussianField};
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+
+    #[test]
+    fn test_moving_average() {
+        // Each value is the kernel weighted sum of the noise beneath it
+        let noise = AlphaStable::new(1.5, 0.5, 1.0, 0.2).unwrap();
+        let kernel = vec![vec![1.0, -0.5], vec![0.25, 2.0]];
+        let field = MovingAverageField::new(kernel.clone(), noise.clone()).unwrap().sample(3, 4, &mut StdRng::seed_from_u64(30));
+        let mut rng = StdRng::seed_from_u64(30);
+        let grid: Vec<Vec<f64>> = (0..4).map(|_| (0..5).map(|_| noise.sample(&mut rng)).collect()).collect();
+        for (i, row) in field.iter().enumerate() {
+            assert_eq!(row.len(), 4);
+            for (j, x) in row.iter().enumerate() {
+                let expected = kernel[0][0] * grid[i + 1][j + 1] + kernel[0][1] * grid[i + 1][j]
+                    + kernel[1][0] * grid[i][j + 1] + kernel[1][1] * grid[i][j];
+                assert!(close(*x, expected, 1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn test_moving_average_marginal() {
+        let noise = AlphaStable::new(1.5, 0.5, 1.0, 0.2).unwrap();
+        let field = MovingAverageField::new(radial_kernel(&|r| (-r).exp(), 1), noise).unwrap();
+        let marginal = field.marginal().unwrap();
+        let (alpha, _, sigma, _, _) = marginal.get_params();
+        let norm = 1.0 + 4.0 * (-1.0_f64).exp().powf(1.5) + 4.0 * (-2.0_f64.sqrt()).exp().powf(1.5);
+        assert_eq!(alpha, 1.5);
+        assert!(close(sigma, norm.powf(1.0 / 1.5), 1e-12));
+
+        // Values across a large field follow the marginal
+        let values: Vec<f64> = field.sample(64, 64, &mut StdRng::seed_from_u64(31)).into_iter().flatten().collect();
+        for x in [-3.0, 0.0, 2.0, 6.0] {
+            let below = values.iter().filter(|v| **v <= x).count() as f64 / values.len() as f64;
+            assert!(close(below, marginal.cdf(x).unwrap(), 0.04));
+        }
+    }
+
+    #[test]
+    fn test_sub_gaussian() {
+        let field = SubGaussianField::new(1.2, vec![vec![1.0, 0.5]]).unwrap();
+        let marginal = field.marginal().unwrap();
+        let (_, _, sigma, _, _) = marginal.get_params();
+        assert!(close(sigma, (0.5 * 1.25_f64).sqrt(), 1e-15));
+
+        // Values at a point across fields follow the marginal
+        let mut rng = StdRng::seed_from_u64(32);
+        let fields: Vec<Vec<Vec<f64>>> = (0..2000).map(|_| field.sample(2, 2, &mut rng)).collect();
+        for x in [-2.0, -0.5, 0.0, 1.0, 4.0] {
+            let below = fields.iter().filter(|f| f[0][0] <= x).count() as f64 / fields.len() as f64;
+            assert!(close(below, marginal.cdf(x).unwrap(), 0.03));
+        }
+
+        // G(0, 0) and G(0, 1) share one noise value with weight 0.5, so corr(G(0, 0), G(0, 1)) = 0.4 and the sign of
+        // X(0, 0) X(0, 1) is positive with probability 1/2 + arcsin(0.4) / pi
+        let positive = fields.iter().filter(|f| f[0][0] * f[0][1] > 0.0).count() as f64 / fields.len() as f64;
+        assert!(close(positive, 0.5 + 0.4_f64.asin() / std::f64::consts::PI, 0.03));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let noise = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        assert!(matches!(MovingAverageField::new(vec![], noise.clone()), Err(Error::DimensionError { dimension: 0 })));
+        assert!(matches!(MovingAverageField::new(vec![vec![1.0], vec![1.0, 2.0]], noise.clone()), Err(Error::LengthMismatchError { .. })));
+        assert!(matches!(MovingAverageField::new(vec![vec![0.0; 2]; 2], noise), Err(Error::ScaleError { .. })));
+        assert!(matches!(SubGaussianField::new(2.0, vec![vec![1.0]]), Err(Error::AlphaError { .. })));
+    }
+}
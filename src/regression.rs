@@ -0,0 +1,277 @@
+use crate::alpha_stable::{close, AlphaStable, Form};
+use crate::error::Error;
+use crate::fit::{fit_mle, initial_steps, log_likelihood, negative_log_likelihood, MIN_SAMPLE_SIZE};
+use crate::linalg::{least_squares, solve};
+use crate::optimize::nelder_mead;
+
+/// Linear regression y = X b + e with alpha stable errors, where each row of X holds the regressors of one observation
+/// and e is i.i.d. S^0_alpha(sigma, beta, 0) in Nolan's form.
+///
+/// Fixing the location of the errors at zero identifies the coefficients; a column of ones in X gives an intercept.
+#[derive(Debug, Clone)]
+pub struct StableRegression {
+    coefficients: Vec<f64>,
+    standard_errors: Vec<f64>,
+    noise: AlphaStable,
+}
+
+impl StableRegression {
+
+    /// Return the coefficients b.
+    pub fn get_coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    /// Return the asymptotic standard errors of the coefficients.
+    pub fn get_standard_errors(&self) -> &[f64] {
+        &self.standard_errors
+    }
+
+    /// Return the error distribution.
+    pub fn get_noise(&self) -> &AlphaStable {
+        &self.noise
+    }
+
+    /// Fitted value x b for the regressors x of one observation.
+    pub fn predict(&self, x: &[f64]) -> f64 {
+        x.iter().zip(&self.coefficients).map(|(x, b)| x * b).sum()
+    }
+
+    /// Residuals y - X b.
+    pub fn residuals(&self, x: &[Vec<f64>], y: &[f64]) -> Vec<f64> {
+        residuals(&self.coefficients, x, y)
+    }
+
+    /// Log likelihood of y given X, i.e. the log likelihood of the residuals.
+    pub fn log_likelihood(&self, x: &[Vec<f64>], y: &[f64]) -> Result<f64, Error> {
+        log_likelihood(&self.noise, &self.residuals(x, y))
+    }
+
+    /// Fit the coefficients and the error distribution jointly by maximum likelihood.
+    ///
+    /// Coefficients are initialised by least absolute deviations, found by iteratively reweighted least squares, which
+    /// unlike ordinary least squares is consistent under infinite variance. The error distribution is initialised by
+    /// [`fit_mle`] on the residuals, and the likelihood then maximised over (alpha, beta, ln sigma, b) by the Nelder-Mead
+    /// method. Standard errors come from the expected Fisher information of the errors, sum_i of the information of
+    /// (alpha, beta, sigma, x_i b) in Nolan's form, so that they allow for the estimation of the error distribution.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rand::thread_rng;
+    /// use alpha_stable::{regression::StableRegression, AlphaStable};
+    ///
+    /// let mut rng = thread_rng();
+    /// let noise = AlphaStable::new_S0(1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let x: Vec<Vec<f64>> = (0..200).map(|i| vec![1.0, 0.1 * i as f64]).collect();
+    /// let y: Vec<f64> = x.iter().map(|row| 2.0 + 0.5 * row[1] + noise.sample(&mut rng)).collect();
+    /// let fitted = StableRegression::fit(&x, &y).unwrap();
+    /// let (b, se) = (fitted.get_coefficients()[1], fitted.get_standard_errors()[1]);
+    /// ```
+    pub fn fit(x: &[Vec<f64>], y: &[f64]) -> Result<StableRegression, Error> {
+
+        let m = x.first().map_or(0, |row| row.len());
+        if m == 0 {
+            return Err(Error::DimensionError { dimension: 0 });
+        }
+        if let Some(row) = x.iter().find(|row| row.len() != m) {
+            return Err(Error::LengthMismatchError { expected: m, found: row.len() });
+        }
+        if y.len() != x.len() {
+            return Err(Error::LengthMismatchError { expected: x.len(), found: y.len() });
+        }
+        if y.len() < m + MIN_SAMPLE_SIZE {
+            return Err(Error::SampleSizeError { n: y.len() });
+        }
+
+        let b = least_absolute_deviations(x, y).ok_or(Error::SingularMatrixError)?;
+        let (alpha, beta, sigma, _, _) = fit_mle(&residuals(&b, x, y))?.get_params();
+
+        // Search over (alpha, beta, ln sigma, b), with steps for b of a tenth of sigma in units of each regressor
+        let mut x0 = vec![alpha, beta, sigma.ln()];
+        x0.extend(&b);
+        let mut steps = initial_steps(&[alpha, beta, sigma.ln(), 0.0])[..3].to_vec();
+        for j in 0..m {
+            let rms = (x.iter().map(|row| row[j] * row[j]).sum::<f64>() / x.len() as f64).sqrt();
+            steps.push(COEFFICIENT_STEP * sigma / rms);
+        }
+        let f = |p: &[f64]| negative_log_likelihood(&[p[0], p[1], p[2], 0.0], &residuals(&p[3..], x, y));
+        let (p, _) = nelder_mead(&f, &x0, &steps, FIT_TOL, FIT_ITERATIONS);
+
+        let noise = AlphaStable::new_S0(p[0], p[1], p[2].exp(), 0.0)?;
+        let coefficients = p[3..].to_vec();
+        let standard_errors = standard_errors(&noise, x)?;
+        Ok(StableRegression { coefficients, standard_errors, noise })
+    }
+}
+
+// Initial Nelder-Mead step for coefficients, relative to sigma.
+const COEFFICIENT_STEP: f64 = 0.1;
+
+// Convergence tolerance of the negative log likelihood.
+const FIT_TOL: f64 = 1e-6;
+
+// Maximum number of Nelder-Mead iterations.
+const FIT_ITERATIONS: usize = 1000;
+
+// Maximum number of reweighted least squares iterations, and relative change in the coefficients at which they stop.
+const LAD_ITERATIONS: usize = 100;
+const LAD_TOL: f64 = 1e-10;
+
+// Smallest absolute residual used in the least absolute deviations weights, relative to the mean absolute residual.
+const LAD_FLOOR: f64 = 1e-8;
+
+fn residuals(b: &[f64], x: &[Vec<f64>], y: &[f64]) -> Vec<f64> {
+    x.iter().zip(y).map(|(row, y)| y - row.iter().zip(b).map(|(x, b)| x * b).sum::<f64>()).collect()
+}
+
+// Least absolute deviations by iteratively reweighted least squares: sum_i |r_i| = sum_i r_i^2 / |r_i|, so each step
+// solves least squares with weights 1 / |r_i| from the previous residuals. Returns None if X is singular.
+fn least_absolute_deviations(x: &[Vec<f64>], y: &[f64]) -> Option<Vec<f64>> {
+
+    let mut b = least_squares(x, y)?;
+    for _ in 0..LAD_ITERATIONS {
+        let r = residuals(&b, x, y);
+        let mean = r.iter().map(|r| r.abs()).sum::<f64>() / r.len() as f64;
+        if mean == 0.0 {
+            break;
+        }
+
+        // Rows scaled by the square roots of the weights
+        let w: Vec<f64> = r.iter().map(|r| 1.0 / r.abs().max(LAD_FLOOR * mean).sqrt()).collect();
+        let rows: Vec<Vec<f64>> = x.iter().zip(&w).map(|(row, w)| row.iter().map(|x| w * x).collect()).collect();
+        let values: Vec<f64> = y.iter().zip(&w).map(|(y, w)| w * y).collect();
+        let next = least_squares(&rows, &values)?;
+
+        let change = next.iter().zip(&b).map(|(n, b)| (n - b).abs() / (1.0 + b.abs())).fold(0.0, f64::max);
+        b = next;
+        if change < LAD_TOL {
+            break;
+        }
+    }
+    Some(b)
+}
+
+// Standard errors of the coefficients from the inverse of the Fisher information of (alpha, beta, sigma, b). The score
+// of b is x_i times the score of mu_0, so observation i contributes the Nolan form information with the mu_0 row and
+// column multiplied by x_i. At alpha == 2 the information of alpha and beta is zero, and at |beta| == 1 that of beta
+// is, so those parameters are held fixed by dropping their rows and columns.
+fn standard_errors(noise: &AlphaStable, x: &[Vec<f64>]) -> Result<Vec<f64>, Error> {
+
+    let information = noise.fisher_information(Form::Nolan)?;
+    let (alpha, beta, ..) = noise.get_params();
+    let tol = noise.get_tol();
+    let free: Vec<usize> = if close(alpha, 2.0, tol.alpha) {
+        vec![2]
+    } else if close(beta.abs(), 1.0, tol.beta) {
+        vec![0, 2]
+    } else {
+        vec![0, 1, 2]
+    };
+
+    let m = x[0].len();
+    let size = free.len() + m;
+    let mut total = vec![vec![0.0; size]; size];
+    for row in x {
+        // Multipliers of the rows and columns of the information of (alpha, beta, sigma, mu_0)
+        let d: Vec<(usize, f64)> = free.iter().map(|i| (*i, 1.0)).chain(row.iter().map(|x| (3, *x))).collect();
+        for (i, (k, d_i)) in d.iter().enumerate() {
+            for (j, (l, d_j)) in d.iter().enumerate() {
+                total[i][j] += d_i * d_j * information[*k][*l];
+            }
+        }
+    }
+
+    let mut errors = Vec::with_capacity(m);
+    for j in free.len()..size {
+        let mut e = vec![0.0; size];
+        e[j] = 1.0;
+        let column = solve(total.clone(), e).ok_or(Error::SingularMatrixError)?;
+        errors.push(column[j].sqrt());
+    }
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+
+    use super::{least_absolute_deviations, standard_errors, StableRegression};
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+
+    #[test]
+    fn test_least_absolute_deviations() {
+        // Exact fit of y = 1 + 2 t apart from two gross outliers, which least absolute deviations ignores
+        let x: Vec<Vec<f64>> = (0..20).map(|t| vec![1.0, t as f64]).collect();
+        let mut y: Vec<f64> = (0..20).map(|t| 1.0 + 2.0 * t as f64).collect();
+        y[3] += 1e6;
+        y[15] -= 50.0;
+        let b = least_absolute_deviations(&x, &y).unwrap();
+        assert!(close(b[0], 1.0, 1e-3));
+        assert!(close(b[1], 2.0, 1e-4));
+    }
+
+    #[test]
+    fn test_fit() {
+        let mut rng = StdRng::seed_from_u64(25);
+        let noise = AlphaStable::new_S0(1.5, 0.0, 1.0, 0.0).unwrap();
+        let x: Vec<Vec<f64>> = (0..150).map(|_| vec![1.0, rng.gen_range(-2.0..2.0)]).collect();
+        let y: Vec<f64> = x.iter().map(|row| 2.0 - 0.5 * row[1] + noise.sample(&mut rng)).collect();
+
+        let fitted = StableRegression::fit(&x, &y).unwrap();
+        for ((b, se), truth) in fitted.get_coefficients().iter().zip(fitted.get_standard_errors()).zip([2.0, -0.5]) {
+            assert!(*se > 0.0 && *se < 0.2);
+            assert!(close(*b, truth, 3.0 * se));
+        }
+        let (alpha, _, sigma, _, mu_0) = fitted.get_noise().get_params();
+        assert!(close(alpha, 1.5, 0.3));
+        assert!(close(sigma, 1.0, 0.2));
+        assert_eq!(mu_0, 0.0);
+        assert!(close(fitted.predict(&[1.0, 1.0]), 1.5, 0.3));
+
+        // The maximum likelihood fit is at least as likely as the truth
+        let truth = StableRegression { coefficients: vec![2.0, -0.5], standard_errors: vec![], noise };
+        assert!(fitted.log_likelihood(&x, &y).unwrap() >= truth.log_likelihood(&x, &y).unwrap());
+    }
+
+    #[test]
+    fn test_gaussian_noise() {
+        // Normal errors are stable with alpha == 2, where the information of alpha and beta vanishes
+        let mut rng = StdRng::seed_from_u64(26);
+        let x: Vec<Vec<f64>> = (0..200).map(|_| vec![1.0, rng.gen_range(-2.0..2.0)]).collect();
+        let y: Vec<f64> = x.iter().map(|row| 2.0 - 0.5 * row[1] + rng.sample::<f64, _>(StandardNormal)).collect();
+
+        let fitted = StableRegression::fit(&x, &y).unwrap();
+        for ((b, se), truth) in fitted.get_coefficients().iter().zip(fitted.get_standard_errors()).zip([2.0, -0.5]) {
+            assert!(se.is_finite() && *se > 0.0 && *se < 0.2);
+            assert!(close(*b, truth, 3.0 * se));
+        }
+        assert!(fitted.get_noise().get_params().0 > 1.8);
+    }
+
+    #[test]
+    fn test_standard_errors_normal() {
+        // At alpha == 2 the errors are N(0, 2 sigma^2), so the standard errors are those of least squares
+        let x: Vec<Vec<f64>> = (0..10).map(|t| vec![1.0, t as f64]).collect();
+        let (sxx, sx, n): (f64, f64, f64) = (285.0, 45.0, 10.0);
+        let det = n * sxx - sx * sx;
+        for alpha in [2.0, 2.0 - 1e-7] {
+            let noise = AlphaStable::new_S0(alpha, 0.3, 1.5, 0.0).unwrap();
+            let errors = standard_errors(&noise, &x).unwrap();
+            assert!(close(errors[0], (4.5 * sxx / det).sqrt(), 1e-9));
+            assert!(close(errors[1], (4.5 * n / det).sqrt(), 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        let x = vec![vec![1.0, 0.0]; 10];
+        assert!(matches!(StableRegression::fit(&[], &[]), Err(Error::DimensionError { dimension: 0 })));
+        assert!(matches!(StableRegression::fit(&x, &[0.0; 9]), Err(Error::LengthMismatchError { expected: 10, found: 9 })));
+        assert!(matches!(StableRegression::fit(&x[..6], &[0.0; 6]), Err(Error::SampleSizeError { n: 6 })));
+        // The second column is zero, so the regressors are collinear
+        assert!(matches!(StableRegression::fit(&x, &[0.0; 10]), Err(Error::SingularMatrixError)));
+    }
+}
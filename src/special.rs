@@ -0,0 +1,125 @@
+use std::f64::consts::PI;
+
+// Complementary error function, from the regularized incomplete gamma function erfc(x) = Q(1/2, x^2) for x >= 0.
+pub(crate) fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+    let x2 = x * x;
+    if x2 < 1.5 {
+        1.0 - gamma_p_series(x2)
+    } else {
+        gamma_q_continued_fraction(x2)
+    }
+}
+
+// Standard normal distribution function.
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+// Standard normal quantile function by Acklam's rational approximation, refined by one step of Halley's method.
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e1, 2.209460984245205e2, -2.759285104469687e2, 1.38357751867269e2, -3.066479806614716e1, 2.506628277459239];
+    const B: [f64; 5] = [-5.447609879822406e1, 1.615858368580409e2, -1.556989798598866e2, 6.680131188771972e1, -1.328068155288572e1];
+    const C: [f64; 6] = [-7.784894002430293e-3, -3.223964580411365e-1, -2.400758277161838, -2.549732539343734, 4.374664141464968, 2.938163982698783];
+    const D: [f64; 4] = [7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996, 3.754408661907416];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = |q: f64| (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0);
+    let x = if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+
+    let e = normal_cdf(x) - p;
+    let u = e * (2.0 * PI).sqrt() * (0.5 * x * x).exp();
+    x - u / (1.0 + 0.5 * x * u)
+}
+
+// Regularized lower incomplete gamma function P(1/2, x) by its series. Converges quickly for x < 3/2.
+fn gamma_p_series(x: f64) -> f64 {
+    let a = 0.5;
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut ap = a;
+    let mut del = 1.0 / a;
+    let mut sum = del;
+    for _ in 0..500 {
+        ap += 1.0;
+        del *= x / ap;
+        sum += del;
+        if del.abs() < sum.abs() * 1e-16 {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - 0.5 * PI.ln()).exp()
+}
+
+// Regularized upper incomplete gamma function Q(1/2, x) by Lentz's continued fraction. Converges quickly for x > 3/2.
+fn gamma_q_continued_fraction(x: f64) -> f64 {
+    let a = 0.5;
+    let tiny = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..500 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = b + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        let del = d * c;
+        h *= del;
+        if (del - 1.0).abs() < 1e-16 {
+            break;
+        }
+    }
+    (-x + a * x.ln() - 0.5 * PI.ln()).exp() * h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{erfc, normal_cdf, normal_quantile};
+    use crate::alpha_stable::close;
+
+    #[test]
+    fn test_erfc() {
+        assert!(close(erfc(0.0), 1.0, 1e-15));
+        assert!(close(erfc(0.5), 0.4795001221869535, 1e-15));
+        assert!(close(erfc(1.0), 0.15729920705028513, 1e-15));
+        assert!(close(erfc(2.0), 0.004677734981047266, 1e-16));
+        assert!(close(erfc(-1.0), 1.8427007929497148, 1e-15));
+        assert!(close(erfc(5.0) / 1.537459794428035e-12, 1.0, 1e-12));
+    }
+
+    #[test]
+    fn test_normal() {
+        assert!(close(normal_cdf(1.0), 0.8413447460685429, 1e-15));
+        assert!(close(normal_quantile(0.975), 1.959963984540054, 1e-14));
+        assert!(close(normal_quantile(1e-10), -6.361340902404056, 1e-12));
+        for p in [1e-6, 0.01, 0.3, 0.5, 0.9, 0.999] {
+            assert!(close(normal_cdf(normal_quantile(p)) / p, 1.0, 1e-13));
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use crate::error::Error;
+
+/// Estimators of the extreme value index xi of the upper tail of data, using its k largest order statistics.
+///
+/// For heavy tailed data P(X > x) ~ c x^(-alpha) and xi = 1 / alpha. Lower tails are estimated from negated data, and
+/// both tails together from absolute values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TailEstimator {
+    /// Hill: the mean of ln(X_(i) / X_(k+1)) over the k largest order statistics X_(1) >= X_(2) >= ...
+    Hill,
+    /// Pickands: ln((X_(k) - X_(2k)) / (X_(2k) - X_(4k))) / ln 2, which requires 4k <= n.
+    Pickands,
+    /// Dekkers, Einmahl and de Haan's moment estimator: M_1 + 1 - 1 / (2 (1 - M_1^2 / M_2)), with M_j the mean of
+    /// ln(X_(i) / X_(k+1))^j over the k largest order statistics.
+    Moment,
+}
+
+impl TailEstimator {
+
+    /// Estimate the extreme value index from the k largest order statistics of data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::tail::TailEstimator;
+    ///
+    /// let data: Vec<f64> = (1..=100).map(|i| (i as f64 / 101.0).powf(-0.5)).collect();
+    /// let estimate = TailEstimator::Hill.estimate(&data, 20).unwrap();
+    /// assert!((estimate.get_alpha() - 2.0).abs() < 0.2);
+    /// ```
+    pub fn estimate(&self, data: &[f64], k: usize) -> Result<TailIndex, Error> {
+        let sorted = descending(data);
+        let xi = self.xi(&sorted, k).ok_or(Error::OrderStatisticError { k, n: data.len() })?;
+        Ok(TailIndex { estimator: *self, k, xi })
+    }
+
+    /// Hill plot data: estimates of the extreme value index against k, for each k the estimator admits.
+    pub fn plot(&self, data: &[f64]) -> Vec<(usize, f64)> {
+        let sorted = descending(data);
+        (1..=self.k_max(sorted.len())).filter_map(|k| self.xi(&sorted, k).map(|xi| (k, xi))).collect()
+    }
+
+    /// Estimate the extreme value index with k chosen automatically.
+    ///
+    /// Estimates are computed for k up to a quarter of the sample, and k is taken at the centre of the window of
+    /// consecutive values over which they vary least, where the plot settles between the noise of few order statistics and
+    /// the bias of many.
+    pub fn estimate_auto(&self, data: &[f64]) -> Result<TailIndex, Error> {
+
+        let sorted = descending(data);
+        let n = sorted.len();
+        let plot: Vec<(usize, f64)> = (1..=self.k_max(n).min(n / 4))
+            .filter_map(|k| self.xi(&sorted, k).map(|xi| (k, xi)))
+            .collect();
+
+        let width = (plot.len() / 10).max(MIN_WINDOW);
+        if plot.len() < width {
+            return Err(Error::SampleSizeError { n });
+        }
+
+        let spread = |window: &[(usize, f64)]| {
+            let mean = window.iter().map(|p| p.1).sum::<f64>() / width as f64;
+            window.iter().map(|p| (p.1 - mean).powi(2)).sum::<f64>()
+        };
+        let start = (0..=plot.len() - width)
+            .min_by(|i, j| spread(&plot[*i..*i + width]).total_cmp(&spread(&plot[*j..*j + width])))
+            .unwrap();
+
+        let (k, xi) = plot[start + width / 2];
+        Ok(TailIndex { estimator: *self, k, xi })
+    }
+
+    // Largest k the estimator admits for a sample of n.
+    fn k_max(&self, n: usize) -> usize {
+        match self {
+            TailEstimator::Hill | TailEstimator::Moment => n.saturating_sub(1),
+            TailEstimator::Pickands => n / 4,
+        }
+    }
+
+    // Estimate from data sorted in descending order, None where k is not admitted or the order statistics used are not
+    // positive or distinct enough to give a finite value.
+    fn xi(&self, sorted: &[f64], k: usize) -> Option<f64> {
+
+        if k == 0 || k > self.k_max(sorted.len()) {
+            return None;
+        }
+
+        let xi = match self {
+            TailEstimator::Hill => log_moment(sorted, k, 1)?,
+            TailEstimator::Pickands => {
+                ((sorted[k - 1] - sorted[2 * k - 1]) / (sorted[2 * k - 1] - sorted[4 * k - 1])).ln() / 2.0_f64.ln()
+            },
+            TailEstimator::Moment => {
+                let (m_1, m_2) = (log_moment(sorted, k, 1)?, log_moment(sorted, k, 2)?);
+                m_1 + 1.0 - 0.5 / (1.0 - m_1 * m_1 / m_2)
+            },
+        };
+        if xi.is_finite() { Some(xi) } else { None }
+    }
+}
+
+/// Estimate of the extreme value index of a tail.
+#[derive(Debug, Clone, Copy)]
+pub struct TailIndex {
+    estimator: TailEstimator,
+    k: usize,
+    xi: f64,
+}
+
+impl TailIndex {
+
+    /// Return the estimator used.
+    pub fn get_estimator(&self) -> TailEstimator {
+        self.estimator
+    }
+
+    /// Return the number of order statistics used.
+    pub fn get_k(&self) -> usize {
+        self.k
+    }
+
+    /// Return the estimate of the extreme value index xi.
+    pub fn get_xi(&self) -> f64 {
+        self.xi
+    }
+
+    /// Return the tail index alpha = 1 / xi, restricted to (0, 2] so that it may seed the fit of an alpha stable
+    /// distribution, for example by fit::fit_mle_with_alpha. Estimates of xi below 1/2 give 2.
+    pub fn get_alpha(&self) -> f64 {
+        if self.xi > 0.5 { 1.0 / self.xi } else { 2.0 }
+    }
+}
+
+// Smallest window of consecutive estimates searched when choosing k.
+const MIN_WINDOW: usize = 5;
+
+fn descending(data: &[f64]) -> Vec<f64> {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    sorted
+}
+
+// Mean of ln(X_(i) / X_(k+1))^j over the k largest order statistics, None unless X_(k+1) is positive.
+fn log_moment(sorted: &[f64], k: usize, j: i32) -> Option<f64> {
+    let threshold = sorted[k];
+    if threshold <= 0.0 {
+        return None;
+    }
+    Some(sorted[..k].iter().map(|x| (x / threshold).ln().powi(j)).sum::<f64>() / k as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::TailEstimator;
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+
+    #[test]
+    fn test_pareto() {
+        // Each estimator is consistent for Pareto data with P(X > x) = x^(-alpha)
+        let mut rng = StdRng::seed_from_u64(14);
+        let alpha = 1.2;
+        let data: Vec<f64> = (0..20000).map(|_| (1.0 - rng.gen::<f64>()).powf(-1.0 / alpha)).collect();
+        for (estimator, tol) in [(TailEstimator::Hill, 0.05), (TailEstimator::Pickands, 0.15), (TailEstimator::Moment, 0.1)] {
+            let estimate = estimator.estimate(&data, 2000).unwrap();
+            assert!(close(estimate.get_alpha(), alpha, tol));
+            assert_eq!(estimate.get_k(), 2000);
+            assert_eq!(estimate.get_estimator(), estimator);
+        }
+    }
+
+    #[test]
+    fn test_stable_tail() {
+        let mut rng = StdRng::seed_from_u64(15);
+        let dist = AlphaStable::new(1.0, 0.0, 1.0, 0.0).unwrap();
+        let data: Vec<f64> = (0..20000).map(|_| dist.sample(&mut rng).abs()).collect();
+        let estimate = TailEstimator::Hill.estimate_auto(&data).unwrap();
+        assert!(close(estimate.get_alpha(), 1.0, 0.1));
+        assert!(estimate.get_k() <= data.len() / 4);
+    }
+
+    #[test]
+    fn test_plot() {
+        let data: Vec<f64> = (1..=40).map(|i| i as f64).collect();
+        let plot = TailEstimator::Hill.plot(&data);
+        assert_eq!(plot.len(), 39);
+        assert_eq!(plot[0], (1, (40.0_f64 / 39.0).ln()));
+        assert_eq!(TailEstimator::Pickands.plot(&data).len(), 10);
+    }
+
+    #[test]
+    fn test_invalid_k() {
+        let data = [3.0, 2.0, 1.0, -1.0];
+        assert!(matches!(TailEstimator::Hill.estimate(&data, 0), Err(Error::OrderStatisticError { k: 0, n: 4 })));
+        assert!(matches!(TailEstimator::Hill.estimate(&data, 3), Err(Error::OrderStatisticError { .. })));
+        assert!(matches!(TailEstimator::Pickands.estimate(&data, 2), Err(Error::OrderStatisticError { .. })));
+        assert!(TailEstimator::Hill.estimate(&data, 2).is_ok());
+    }
+}
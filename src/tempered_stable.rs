@@ -0,0 +1,419 @@
+use std::f64::consts::PI;
+
+use num_complex::Complex64;
+use rand::Rng;
+use spfunc::gamma::gamma;
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+use crate::fft::fft;
+use crate::integrator::Integrator;
+
+/// Defines a classical tempered stable (CTS / KoBoL) distribution.
+///
+/// The Levy measure is that of an alpha stable law with its tails damped exponentially:
+///
+/// nu(dx) = c_plus exp(-lambda_plus x) x^(-1-alpha) dx for x > 0 and c_minus exp(-lambda_minus |x|) |x|^(-1-alpha) dx for x < 0.
+///
+/// The distribution is centred so that its mean is mu. All moments are finite.
+#[derive(Debug)]
+pub struct TemperedStable {
+    alpha: f64,
+    c_plus: f64,
+    c_minus: f64,
+    lambda_plus: f64,
+    lambda_minus: f64,
+    mu: f64,
+    tail_plus: Option<Tail>,
+    tail_minus: Option<Tail>,
+    integrator: Integrator,
+}
+
+impl TemperedStable {
+
+    /// Create distribution from alpha, tail constants (c_plus, c_minus), tempering parameters (lambda_plus, lambda_minus) and mean mu.
+    pub fn new(alpha: f64, c_plus: f64, c_minus: f64, lambda_plus: f64, lambda_minus: f64, mu: f64) -> Result<TemperedStable, Error> {
+
+        if alpha <= 0.0 || alpha >= 2.0 || alpha == 1.0 {
+            return Err(Error::TemperedAlphaError { alpha });
+        }
+
+        if c_plus < 0.0 || c_minus < 0.0 || c_plus + c_minus <= 0.0 {
+            return Err(Error::TailConstantError { c_plus, c_minus });
+        }
+
+        for lambda in [lambda_plus, lambda_minus] {
+            if lambda <= 0.0 {
+                return Err(Error::TemperingError { lambda });
+            }
+        }
+
+        let tail_plus = Tail::new(alpha, c_plus, lambda_plus)?;
+        let tail_minus = Tail::new(alpha, c_minus, lambda_minus)?;
+
+        Ok(TemperedStable { alpha, c_plus, c_minus, lambda_plus, lambda_minus, mu, tail_plus, tail_minus, integrator: Integrator::default() })
+    }
+
+    /// Create distribution in the CGMY parameterisation: c_plus = c_minus = C, lambda_minus = G, lambda_plus = M and alpha = Y.
+    pub fn new_cgmy(c: f64, g: f64, m: f64, y: f64, mu: f64) -> Result<TemperedStable, Error> {
+        TemperedStable::new(y, c, c, m, g, mu)
+    }
+
+    /// Set integration parameters.
+    pub fn with_integrator(&mut self, integrator: Integrator) -> &mut Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// Return parameters as tuple of (alpha, c_plus, c_minus, lambda_plus, lambda_minus, mu).
+    pub fn get_params(&self) -> (f64, f64, f64, f64, f64, f64) {
+        (self.alpha, self.c_plus, self.c_minus, self.lambda_plus, self.lambda_minus, self.mu)
+    }
+
+    /// Characteristic function E[exp(iuX)].
+    pub fn characteristic_function(&self, u: f64) -> Complex64 {
+        self.log_characteristic_function(u).exp()
+    }
+
+    fn log_characteristic_function(&self, u: f64) -> Complex64 {
+        let a = self.alpha;
+        let g = gamma_neg(a);
+        let iu = Complex64::new(0.0, u);
+
+        let plus = (Complex64::new(self.lambda_plus, 0.0) - iu).powf(a) - self.lambda_plus.powf(a) + iu * a * self.lambda_plus.powf(a - 1.0);
+        let minus = (Complex64::new(self.lambda_minus, 0.0) + iu).powf(a) - self.lambda_minus.powf(a) - iu * a * self.lambda_minus.powf(a - 1.0);
+
+        iu * self.mu + self.c_plus * g * plus + self.c_minus * g * minus
+    }
+
+    /// Value of Probability Distribution function at x, obtained by inverting the characteristic function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let distribution = alpha_stable::TemperedStable::new(0.7, 1.0, 1.0, 2.0, 2.0, 0.0).unwrap();
+    /// let val = distribution.pdf(0.5).unwrap();
+    /// ```
+    pub fn pdf(&self, x: f64) -> Result<f64, Error> {
+        let val = self.integrator.integrate_range(
+            &|u| {
+                (self.log_characteristic_function(u) - Complex64::new(0.0, u * x)).exp().re
+            },
+            0.0, f64::INFINITY,
+        )?;
+        Ok((val / PI).max(0.0))
+    }
+
+    /// Values of the Probability Distribution function on the grid x_k = mu + (k - n/2) h, k = 0..n, computed by FFT.
+    ///
+    /// n must be a power of two. Returns the tuple (x, pdf).
+    pub fn pdf_grid(&self, n: usize, h: f64) -> Result<(Vec<f64>, Vec<f64>), Error> {
+
+        if !n.is_power_of_two() {
+            return Err(Error::GridSizeError { n });
+        }
+
+        let x_0 = self.mu - 0.5 * n as f64 * h;
+        let du = 2.0 * PI / (n as f64 * h);
+
+        let mut buf: Vec<Complex64> = (0..n).map(|j| {
+            let u = (j as f64 - 0.5 * n as f64) * du;
+            (self.log_characteristic_function(u) - Complex64::new(0.0, u * x_0)).exp()
+        }).collect();
+
+        fft(&mut buf, false);
+
+        let x = (0..n).map(|k| x_0 + k as f64 * h).collect();
+        let pdf = buf.iter().enumerate().map(|(k, z)| {
+            let sign = (-1.0_f64).powi(k as i32);
+            (sign * z.re * du / (2.0 * PI)).max(0.0)
+        }).collect();
+
+        Ok((x, pdf))
+    }
+
+    /// Sample from the distribution.
+    ///
+    /// Each tail is the sum of independent pieces with tail constant c / m, where m is chosen so that c lambda^alpha / m is
+    /// of order one. Each piece is sampled exactly by exponentially tilting a totally skewed stable variable drawn with
+    /// `AlphaStable::sample` and accepting with probability exp(-lambda x).
+    ///
+    /// The rejection step needs the stable variable to be bounded below, so exact sampling is only available for alpha < 1.
+    /// Returns `Error::ExactSamplerError` for 1 < alpha < 2; use `sample_approximate` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    ///
+    /// let mut rng = thread_rng();
+    /// let distribution = alpha_stable::TemperedStable::new(0.7, 1.0, 1.0, 2.0, 2.0, 0.0).unwrap();
+    /// let sample = distribution.sample(&mut rng).unwrap();
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<f64, Error> {
+        if self.alpha > 1.0 {
+            return Err(Error::ExactSamplerError { alpha: self.alpha });
+        }
+        Ok(self.sample_approximate(rng))
+    }
+
+    /// Sample from the distribution, approximately when 1 < alpha < 2.
+    ///
+    /// For alpha < 1 this is the exact sampler used by `sample`. For 1 < alpha < 2 the stable variable is unbounded below
+    /// and each piece is accepted with probability min(1, exp(-lambda (x - x_f))), where x_f lies four standard deviations
+    /// below the mean of the tilted law (Baeumer and Meerschaert). Draws below x_f are over-weighted, so the sampled law
+    /// differs slightly from the tempered one. Pieces are made small enough that lambda times their standard deviation is
+    /// at most 1/4, which keeps the acceptance rate bounded but makes each draw cost more as c lambda^alpha grows.
+    pub fn sample_approximate<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let mut x = self.mu;
+        if let Some(tail) = &self.tail_plus {
+            x += tail.sample(self.alpha, rng);
+        }
+        if let Some(tail) = &self.tail_minus {
+            x -= tail.sample(self.alpha, rng);
+        }
+        x
+    }
+
+    /// Cumulant of order n. The first cumulant is the mean.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is zero.
+    pub fn cumulant(&self, n: u32) -> f64 {
+        assert!(n >= 1, "cumulant order must be at least 1");
+        if n == 1 {
+            return self.mu;
+        }
+        let sign = (-1.0_f64).powi(n as i32);
+        let g = gamma(n as f64 - self.alpha);
+        self.c_plus * g * self.lambda_plus.powf(self.alpha - n as f64) +
+            sign * self.c_minus * g * self.lambda_minus.powf(self.alpha - n as f64)
+    }
+
+    /// Mean of the distribution.
+    pub fn mean(&self) -> f64 {
+        self.mu
+    }
+
+    /// Variance of the distribution.
+    pub fn variance(&self) -> f64 {
+        self.cumulant(2)
+    }
+
+    /// Skewness of the distribution.
+    pub fn skewness(&self) -> f64 {
+        self.cumulant(3) / self.cumulant(2).powf(1.5)
+    }
+
+    /// Excess kurtosis of the distribution.
+    pub fn kurtosis(&self) -> f64 {
+        self.cumulant(4) / self.cumulant(2).powi(2)
+    }
+}
+
+// Largest value of lambda times the standard deviation of a tilted piece used by the approximate sampler.
+const APPROXIMATE_PIECE_SD: f64 = 0.25;
+
+// One tail of the distribution, sampled as the centred sum of `pieces` independent one sided tempered stable variables
+// with tail constant c.
+#[derive(Debug)]
+struct Tail {
+    stable: AlphaStable,
+    pieces: usize,
+    c: f64,
+    lambda: f64,
+}
+
+impl Tail {
+
+    fn new(alpha: f64, c: f64, lambda: f64) -> Result<Option<Tail>, Error> {
+        if c == 0.0 {
+            return Ok(None);
+        }
+
+        // The acceptance probability of a piece is exp(c Gamma(-alpha) lambda^alpha / pieces).
+        let scale = c * lambda.powf(alpha);
+        let mut pieces = (scale * gamma_neg(alpha).abs()).ceil().max(1.0);
+        if alpha > 1.0 {
+            pieces = pieces.max((scale * gamma(2.0 - alpha) / APPROXIMATE_PIECE_SD.powi(2)).ceil());
+        }
+
+        let c = c / pieces;
+        let sigma = (-c * gamma_neg(alpha) * (0.5 * PI * alpha).cos()).powf(1.0 / alpha);
+        let stable = AlphaStable::new(alpha, 1.0, sigma, 0.0)?;
+
+        Ok(Some(Tail { stable, pieces: pieces as usize, c, lambda }))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, alpha: f64, rng: &mut R) -> f64 {
+        (0..self.pieces).map(|_| self.sample_piece(alpha, rng)).sum()
+    }
+
+    // Samples a centred piece by rejection from the totally skewed stable law S_alpha(sigma, 1, 0), whose Levy measure
+    // is c x^(-1-alpha) dx on x > 0.
+    fn sample_piece<R: Rng + ?Sized>(&self, alpha: f64, rng: &mut R) -> f64 {
+
+        let mean = self.c * gamma_one_minus(alpha) * self.lambda.powf(alpha - 1.0);
+
+        let floor = if alpha < 1.0 {
+            0.0
+        } else {
+            mean - 4.0 * (self.c * gamma(2.0 - alpha) * self.lambda.powf(alpha - 2.0)).sqrt()
+        };
+
+        loop {
+            let y = self.stable.sample(rng);
+            if rng.gen::<f64>() <= (-self.lambda * (y - floor)).exp() {
+                return y - mean;
+            }
+        }
+    }
+}
+
+// Gamma(-alpha) for alpha in (0,1) or (1,2) by recurrence from a positive argument.
+fn gamma_neg(alpha: f64) -> f64 {
+    gamma(2.0 - alpha) / (alpha * (alpha - 1.0))
+}
+
+// Gamma(1-alpha) for alpha in (0,1) or (1,2) by recurrence from a positive argument.
+fn gamma_one_minus(alpha: f64) -> f64 {
+    gamma(2.0 - alpha) / (1.0 - alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::TemperedStable;
+    use crate::alpha_stable::close;
+    use crate::error::Error;
+
+    fn sample_mean_var(samples: &[f64]) -> (f64, f64) {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        (mean, var)
+    }
+
+    #[test]
+    fn test_parameter_errors() {
+        for alpha in [0.0, 1.0, 2.0, 2.5] {
+            assert!(matches!(TemperedStable::new(alpha, 1.0, 1.0, 1.0, 1.0, 0.0), Err(Error::TemperedAlphaError { .. })));
+        }
+        for (c_plus, c_minus) in [(-1.0, 1.0), (1.0, -1.0), (0.0, 0.0)] {
+            assert!(matches!(TemperedStable::new(0.5, c_plus, c_minus, 1.0, 1.0, 0.0), Err(Error::TailConstantError { .. })));
+        }
+        for (lambda_plus, lambda_minus) in [(0.0, 1.0), (1.0, -1.0)] {
+            assert!(matches!(TemperedStable::new(0.5, 1.0, 1.0, lambda_plus, lambda_minus, 0.0), Err(Error::TemperingError { .. })));
+        }
+    }
+
+    #[test]
+    fn test_grid_size_error() {
+        let dist = TemperedStable::new(0.5, 1.0, 1.0, 1.0, 1.0, 0.0).unwrap();
+        assert!(matches!(dist.pdf_grid(1000, 0.01), Err(Error::GridSizeError { n: 1000 })));
+    }
+
+    #[test]
+    fn test_cgmy_mapping() {
+        let dist = TemperedStable::new_cgmy(0.5, 2.0, 3.0, 0.7, 0.1).unwrap();
+        assert_eq!(dist.get_params(), (0.7, 0.5, 0.5, 3.0, 2.0, 0.1));
+    }
+
+    #[test]
+    fn test_characteristic_function() {
+        let dist = TemperedStable::new(0.5, 1.0, 2.0, 1.0, 2.0, 0.3).unwrap();
+        let phi = dist.characteristic_function(0.0);
+        assert!(close(phi.re, 1.0, 1e-14) && close(phi.im, 0.0, 1e-14));
+
+        // As lambda tends to zero the law tends to the stable law S_alpha(sigma, beta, 0) with
+        // sigma^alpha = -(c_plus + c_minus) Gamma(-alpha) cos(pi alpha / 2) and beta = (c_plus - c_minus) / (c_plus + c_minus).
+        let (alpha, c_plus, c_minus) = (1.5, 1.0, 0.5);
+        let dist = TemperedStable::new(alpha, c_plus, c_minus, 1e-10, 1e-10, 0.0).unwrap();
+        let gamma_neg = 2.363271801207355;
+        let sigma_alpha = -(c_plus + c_minus) * gamma_neg * (0.5 * PI * alpha).cos();
+        let beta = (c_plus - c_minus) / (c_plus + c_minus);
+        for u in [-2.0, -0.5, 0.7, 3.0_f64] {
+            let phi = dist.characteristic_function(u);
+            let re = -sigma_alpha * u.abs().powf(alpha);
+            let im = sigma_alpha * u.abs().powf(alpha) * beta * u.signum() * (0.5 * PI * alpha).tan();
+            let expected = num_complex::Complex64::new(re, im).exp();
+            assert!(close(phi.re, expected.re, 1e-4) && close(phi.im, expected.im, 1e-4));
+        }
+    }
+
+    #[test]
+    fn test_cumulants() {
+        let dist = TemperedStable::new(0.5, 1.0, 2.0, 1.0, 2.0, 0.3).unwrap();
+
+        // Gamma(1.5), Gamma(2.5) and Gamma(3.5)
+        let k2 = 0.886226925452758 * (1.0 + 2.0 * 2.0_f64.powf(-1.5));
+        let k3 = 1.329340388179137 * (1.0 - 2.0 * 2.0_f64.powf(-2.5));
+        let k4 = 3.323350970447843 * (1.0 + 2.0 * 2.0_f64.powf(-3.5));
+
+        assert!(close(dist.cumulant(1), 0.3, 1e-12));
+        assert!(close(dist.mean(), 0.3, 1e-12));
+        assert!(close(dist.cumulant(2), k2, 1e-8));
+        assert!(close(dist.variance(), k2, 1e-8));
+        assert!(close(dist.cumulant(3), k3, 1e-8));
+        assert!(close(dist.cumulant(4), k4, 1e-8));
+        assert!(close(dist.skewness(), k3 / k2.powf(1.5), 1e-8));
+        assert!(close(dist.kurtosis(), k4 / (k2 * k2), 1e-8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cumulant_zero() {
+        let dist = TemperedStable::new(0.5, 1.0, 2.0, 1.0, 2.0, 0.3).unwrap();
+        dist.cumulant(0);
+    }
+
+    #[test]
+    fn test_pdf_inversion_matches_fft() {
+        let dist = TemperedStable::new(1.5, 1.0, 0.5, 1.0, 2.0, 0.3).unwrap();
+        let (x, pdf) = dist.pdf_grid(1024, 0.02).unwrap();
+        for k in [400, 512, 600] {
+            assert!(close(dist.pdf(x[k]).unwrap(), pdf[k], 1e-6));
+        }
+        let total: f64 = pdf.iter().sum::<f64>() * 0.02;
+        assert!(close(total, 1.0, 1e-6));
+    }
+
+    #[test]
+    fn test_sample_moments() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let dist = TemperedStable::new(0.6, 0.2, 0.1, 1.0, 2.0, 1.0).unwrap();
+        let n = 20000;
+        let samples: Vec<f64> = (0..n).map(|_| dist.sample(&mut rng).unwrap()).collect();
+        let (mean, var) = sample_mean_var(&samples);
+        assert!(close(mean, dist.mean(), 4.0 * (dist.variance() / n as f64).sqrt()));
+        assert!(close(var, dist.variance(), 0.1 * dist.variance()));
+    }
+
+    #[test]
+    fn test_sample_large_tail_constant() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let dist = TemperedStable::new(0.7, 5.0, 5.0, 5.0, 5.0, 0.0).unwrap();
+        let n = 5000;
+        let samples: Vec<f64> = (0..n).map(|_| dist.sample(&mut rng).unwrap()).collect();
+        let (mean, var) = sample_mean_var(&samples);
+        assert!(close(mean, dist.mean(), 4.0 * (dist.variance() / n as f64).sqrt()));
+        assert!(close(var, dist.variance(), 0.1 * dist.variance()));
+    }
+
+    #[test]
+    fn test_sample_approximate() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let dist = TemperedStable::new(1.4, 0.5, 0.2, 1.0, 2.0, 1.0).unwrap();
+        assert!(matches!(dist.sample(&mut rng), Err(Error::ExactSamplerError { .. })));
+
+        let n = 20000;
+        let samples: Vec<f64> = (0..n).map(|_| dist.sample_approximate(&mut rng)).collect();
+        let (mean, var) = sample_mean_var(&samples);
+        assert!(close(mean, dist.mean(), 4.0 * (dist.variance() / n as f64).sqrt()));
+        assert!(close(var, dist.variance(), 0.1 * dist.variance()));
+    }
+}
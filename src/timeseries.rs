@@ -0,0 +1,430 @@
+use std::cmp::Ordering;
+
+use rand::Rng;
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+use crate::fit::{fit_mle, initial_estimate, log_likelihood};
+use crate::linalg::least_squares;
+use crate::optimize::nelder_mead;
+
+/// ARMA(p,q) model with alpha stable innovations:
+/// X(t) = ar_1 X(t-1) + ... + ar_p X(t-p) + e(t) + ma_1 e(t-1) + ... + ma_q e(t-q), with e(t) i.i.d. from the noise distribution.
+///
+/// The location of the noise plays the role of an intercept.
+#[derive(Debug)]
+pub struct StableArma {
+    ar: Vec<f64>,
+    ma: Vec<f64>,
+    noise: AlphaStable,
+}
+
+impl StableArma {
+
+    /// Create model from autoregressive and moving average coefficients and the innovation distribution.
+    pub fn new(ar: Vec<f64>, ma: Vec<f64>, noise: AlphaStable) -> StableArma {
+        StableArma { ar, ma, noise }
+    }
+
+    /// Return autoregressive coefficients.
+    pub fn get_ar(&self) -> &[f64] {
+        &self.ar
+    }
+
+    /// Return moving average coefficients.
+    pub fn get_ma(&self) -> &[f64] {
+        &self.ma
+    }
+
+    /// Return innovation distribution.
+    pub fn get_noise(&self) -> &AlphaStable {
+        &self.noise
+    }
+
+    /// Simulate n values after discarding a burn-in period started from zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::{timeseries::StableArma, AlphaStable};
+    ///
+    /// let mut rng = thread_rng();
+    /// let noise = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+    /// let model = StableArma::new(vec![0.5], vec![0.3], noise);
+    /// let data = model.simulate(500, &mut rng);
+    /// ```
+    pub fn simulate<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<f64> {
+
+        let burn_in = BURN_IN + self.ar.len() + self.ma.len();
+        let e: Vec<f64> = (0..n + burn_in).map(|_| self.noise.sample(rng)).collect();
+        let mut x = vec![0.0; n + burn_in];
+        for t in 0..x.len() {
+            let ar: f64 = self.ar.iter().enumerate().filter(|(i, _)| *i < t).map(|(i, a)| a * x[t - i - 1]).sum();
+            let ma: f64 = self.ma.iter().enumerate().filter(|(j, _)| *j < t).map(|(j, b)| b * e[t - j - 1]).sum();
+            x[t] = ar + e[t] + ma;
+        }
+        x.split_off(burn_in)
+    }
+
+    /// Innovations implied by data, computed recursively from t = p with pre-sample innovations set to zero.
+    pub fn residuals(&self, data: &[f64]) -> Vec<f64> {
+        residuals(&self.ar, &self.ma, data)
+    }
+
+    /// Conditional log likelihood of data, i.e. the log likelihood of the residuals.
+    pub fn log_likelihood(&self, data: &[f64]) -> Result<f64, Error> {
+        log_likelihood(&self.noise, &self.residuals(data))
+    }
+
+    /// Fit an ARMA(p,q) model with stable innovations by conditional maximum likelihood.
+    ///
+    /// Coefficients are initialised by the Hannan-Rissanen least squares method. The innovation distribution is then
+    /// fitted to the residuals with [`fit_mle`], the coefficients and innovation location re-estimated by maximising the
+    /// stable likelihood of the residuals, and the innovation distribution finally refitted to the new residuals.
+    pub fn fit(data: &[f64], p: usize, q: usize) -> Result<StableArma, Error> {
+
+        let long_order = if q > 0 { (p + q + 1).max(LONG_AR_ORDER) } else { p };
+        if data.len() < long_order + q + MIN_RESIDUALS {
+            return Err(Error::SampleSizeError { n: data.len() });
+        }
+
+        let mut coefficients = hannan_rissanen(data, p, q, long_order).unwrap_or_else(|| vec![0.0; p + q]);
+        let noise = fit_mle(&residuals(&coefficients[..p], &coefficients[p..], data))?;
+        if p + q == 0 {
+            return Ok(StableArma { ar: Vec::new(), ma: Vec::new(), noise });
+        }
+
+        // Maximise over coefficients and innovation location with the shape and scale of the innovations held fixed
+        let (alpha, beta, sigma, _, mu_0) = noise.get_params();
+        let f = |c: &[f64]| {
+            let noise = match AlphaStable::new_S0(alpha, beta, sigma, c[p + q]) {
+                Ok(noise) => noise,
+                Err(_) => return f64::INFINITY,
+            };
+            match log_likelihood(&noise, &residuals(&c[..p], &c[p..p + q], data)) {
+                Ok(val) => -val,
+                Err(_) => f64::INFINITY,
+            }
+        };
+
+        coefficients.push(mu_0);
+        let mut steps = vec![COEFFICIENT_STEP; p + q];
+        steps.push(0.1 * sigma);
+        let (mut coefficients, _) = nelder_mead(&f, &coefficients, &steps, FIT_TOL, FIT_ITERATIONS);
+        coefficients.truncate(p + q);
+
+        let noise = fit_mle(&residuals(&coefficients[..p], &coefficients[p..], data))?;
+        let ma = coefficients.split_off(p);
+        Ok(StableArma { ar: coefficients, ma, noise })
+    }
+}
+
+// Number of simulated values discarded before the first returned value.
+const BURN_IN: usize = 200;
+
+// Minimum order of the long autoregression used to estimate innovations for Hannan-Rissanen.
+const LONG_AR_ORDER: usize = 10;
+
+// Minimum number of residuals required to fit a model.
+const MIN_RESIDUALS: usize = 20;
+
+// Initial Nelder-Mead step for ARMA coefficients.
+const COEFFICIENT_STEP: f64 = 0.05;
+
+// Convergence tolerance of the negative log likelihood.
+const FIT_TOL: f64 = 1e-6;
+
+// Maximum number of Nelder-Mead iterations.
+const FIT_ITERATIONS: usize = 500;
+
+fn residuals(ar: &[f64], ma: &[f64], data: &[f64]) -> Vec<f64> {
+    let p = ar.len();
+    let mut e: Vec<f64> = Vec::with_capacity(data.len().saturating_sub(p));
+    for t in p..data.len() {
+        let ar_part: f64 = ar.iter().enumerate().map(|(i, a)| a * data[t - i - 1]).sum();
+        let ma_part: f64 = ma.iter().enumerate().filter(|(j, _)| *j < e.len()).map(|(j, b)| b * e[e.len() - j - 1]).sum();
+        e.push(data[t] - ar_part - ma_part);
+    }
+    e
+}
+
+// Regresses each value on a constant and the given number of lags of a sequence and, optionally, of estimated innovations.
+// Returns the coefficients of the lags.
+fn lag_regression(data: &[f64], p: usize, innovations: Option<(&[f64], usize)>, start: usize) -> Option<Vec<f64>> {
+    let (e, q) = innovations.unwrap_or((&[], 0));
+    let rows: Vec<Vec<f64>> = (start..data.len()).map(|t| {
+        let mut row = vec![1.0];
+        row.extend((1..=p).map(|i| data[t - i]));
+        row.extend((1..=q).map(|j| e[t - j]));
+        row
+    }).collect();
+    let c = least_squares(&rows, &data[start..])?;
+    Some(c[1..].to_vec())
+}
+
+// Hannan-Rissanen estimates of the AR then MA coefficients: innovations are estimated from a long autoregression
+// and used as regressors.
+fn hannan_rissanen(data: &[f64], p: usize, q: usize, long_order: usize) -> Option<Vec<f64>> {
+    if q == 0 {
+        return lag_regression(data, p, None, p);
+    }
+    let long_ar = lag_regression(data, long_order, None, long_order)?;
+    let mut e = vec![0.0; long_order];
+    e.extend(residuals(&long_ar, &[], data));
+    lag_regression(data, p, Some((&e, q)), long_order + q)
+}
+
+/// GARCH(p,q) model with alpha stable innovations, in the absolute value (power one) form suited to infinite variance:
+/// r(t) = mu + sigma(t) z(t), with sigma(t) = omega + arch_1 |r(t-1) - mu| + ... + arch_p |r(t-p) - mu| + garch_1 sigma(t-1) + ... + garch_q sigma(t-q)
+/// and z(t) i.i.d. from the standardised innovation distribution.
+#[derive(Debug)]
+pub struct StableGarch {
+    omega: f64,
+    arch: Vec<f64>,
+    garch: Vec<f64>,
+    mu: f64,
+    innovation: AlphaStable,
+}
+
+impl StableGarch {
+
+    /// Create model. omega must be positive and the arch and garch coefficients non-negative.
+    pub fn new(omega: f64, arch: Vec<f64>, garch: Vec<f64>, mu: f64, innovation: AlphaStable) -> Result<StableGarch, Error> {
+        if omega.partial_cmp(&0.0) != Some(Ordering::Greater) {
+            return Err(Error::GarchParameterError { value: omega });
+        }
+        if let Some(value) = arch.iter().chain(garch.iter()).find(|c| c.partial_cmp(&&0.0).is_none_or(Ordering::is_lt)) {
+            return Err(Error::GarchParameterError { value: *value });
+        }
+        Ok(StableGarch { omega, arch, garch, mu, innovation })
+    }
+
+    /// Create GARCH(1,1) model.
+    pub fn new_garch_1_1(omega: f64, arch: f64, garch: f64, mu: f64, innovation: AlphaStable) -> Result<StableGarch, Error> {
+        StableGarch::new(omega, vec![arch], vec![garch], mu, innovation)
+    }
+
+    /// Return parameters as tuple of (omega, arch coefficients, garch coefficients, mu).
+    pub fn get_params(&self) -> (f64, &[f64], &[f64], f64) {
+        (self.omega, &self.arch, &self.garch, self.mu)
+    }
+
+    /// Return standardised innovation distribution.
+    pub fn get_innovation(&self) -> &AlphaStable {
+        &self.innovation
+    }
+
+    /// Simulate n returns after discarding a burn-in period.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::{timeseries::StableGarch, AlphaStable};
+    ///
+    /// let mut rng = thread_rng();
+    /// let innovation = AlphaStable::new_S0(1.8, 0.0, 1.0, 0.0).unwrap();
+    /// let model = StableGarch::new_garch_1_1(0.1, 0.1, 0.8, 0.0, innovation).unwrap();
+    /// let returns = model.simulate(500, &mut rng);
+    /// ```
+    pub fn simulate<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<f64> {
+
+        let burn_in = BURN_IN + self.arch.len() + self.garch.len();
+        let start = self.omega / (1.0 - self.garch.iter().sum::<f64>()).max(f64::EPSILON);
+        let mut shocks = Vec::with_capacity(n + burn_in);
+        let mut deviations = Vec::with_capacity(n + burn_in);
+        let mut sigmas = Vec::with_capacity(n + burn_in);
+        for _ in 0..n + burn_in {
+            let sigma = next_volatility(self.omega, &self.arch, &self.garch, &deviations, &sigmas, start);
+            let shock = sigma * self.innovation.sample(rng);
+            shocks.push(shock);
+            deviations.push(shock.abs());
+            sigmas.push(sigma);
+        }
+        shocks.split_off(burn_in).iter().map(|e| self.mu + e).collect()
+    }
+
+    /// Conditional scales sigma(t) implied by returns. Pre-sample values of |r - mu| and sigma are set to the mean absolute deviation of the returns.
+    pub fn volatilities(&self, data: &[f64]) -> Vec<f64> {
+        volatilities(self.omega, &self.arch, &self.garch, self.mu, data)
+    }
+
+    /// Quasi log likelihood of returns: the sum of ln f(z(t)) - ln sigma(t), where f is the innovation density and z(t) = (r(t) - mu) / sigma(t).
+    pub fn log_likelihood(&self, data: &[f64]) -> Result<f64, Error> {
+        quasi_log_likelihood(self.omega, &self.arch, &self.garch, self.mu, &self.innovation, data)
+    }
+
+    /// Fit a GARCH(p,q) model with stable innovations by quasi maximum likelihood.
+    ///
+    /// Innovations are S^0_alpha(1, beta, 0). Alpha and beta are initialised from the empirical characteristic function of the
+    /// returns, and the volatility parameters estimated by maximising the quasi likelihood. Alpha and beta are then fitted
+    /// to the standardised residuals with [`fit_mle`] and the volatility parameters re-estimated.
+    pub fn fit(data: &[f64], p: usize, q: usize) -> Result<StableGarch, Error> {
+
+        if data.len() < p.max(q) + MIN_RESIDUALS {
+            return Err(Error::SampleSizeError { n: data.len() });
+        }
+
+        let (alpha, beta, _, _, mu) = initial_estimate(data)?.get_params();
+        let scale = data.iter().map(|r| (r - mu).abs()).sum::<f64>() / data.len() as f64;
+        let mut model = StableGarch {
+            omega: INITIAL_OMEGA * scale,
+            arch: vec![INITIAL_ARCH / p.max(1) as f64; p],
+            garch: vec![INITIAL_GARCH / q.max(1) as f64; q],
+            mu,
+            innovation: AlphaStable::new_S0(alpha, beta, 1.0, 0.0)?,
+        };
+        model.fit_volatility(data, scale);
+
+        let z: Vec<f64> = data.iter().zip(model.volatilities(data)).map(|(r, sigma)| (r - model.mu) / sigma).collect();
+        let (alpha, beta, _, _, _) = fit_mle(&z)?.get_params();
+        model.innovation = AlphaStable::new_S0(alpha, beta, 1.0, 0.0)?;
+        model.fit_volatility(data, scale);
+
+        Ok(model)
+    }
+
+    // Maximises the quasi likelihood over (ln omega, arch, garch, mu) with the innovation distribution held fixed.
+    fn fit_volatility(&mut self, data: &[f64], scale: f64) {
+
+        let (p, q) = (self.arch.len(), self.garch.len());
+        let f = |c: &[f64]| {
+            let (arch, garch) = (&c[1..1 + p], &c[1 + p..1 + p + q]);
+            if arch.iter().chain(garch.iter()).any(|a| *a < 0.0) || garch.iter().sum::<f64>() >= 1.0 {
+                return f64::INFINITY;
+            }
+            match quasi_log_likelihood(c[0].exp(), arch, garch, c[1 + p + q], &self.innovation, data) {
+                Ok(val) => -val,
+                Err(_) => f64::INFINITY,
+            }
+        };
+
+        let mut x0 = vec![self.omega.ln()];
+        x0.extend(self.arch.iter().chain(self.garch.iter()));
+        x0.push(self.mu);
+        let mut steps = vec![0.2];
+        steps.extend(vec![COEFFICIENT_STEP; p + q]);
+        steps.push(0.1 * scale);
+        let (c, _) = nelder_mead(&f, &x0, &steps, FIT_TOL, FIT_ITERATIONS);
+
+        self.omega = c[0].exp();
+        self.arch = c[1..1 + p].to_vec();
+        self.garch = c[1 + p..1 + p + q].to_vec();
+        self.mu = c[1 + p + q];
+    }
+}
+
+// Initial omega of a GARCH fit as a fraction of the mean absolute deviation of the returns.
+const INITIAL_OMEGA: f64 = 0.1;
+
+// Initial sum of arch coefficients of a GARCH fit.
+const INITIAL_ARCH: f64 = 0.1;
+
+// Initial sum of garch coefficients of a GARCH fit.
+const INITIAL_GARCH: f64 = 0.8;
+
+// Next conditional scale given the absolute deviations and scales so far. Values before the start of the histories are set to start.
+fn next_volatility(omega: f64, arch: &[f64], garch: &[f64], deviations: &[f64], sigmas: &[f64], start: f64) -> f64 {
+    let lag = |history: &[f64], i: usize| if i < history.len() { history[history.len() - i - 1] } else { start };
+    omega + arch.iter().enumerate().map(|(i, a)| a * lag(deviations, i)).sum::<f64>()
+        + garch.iter().enumerate().map(|(j, b)| b * lag(sigmas, j)).sum::<f64>()
+}
+
+fn volatilities(omega: f64, arch: &[f64], garch: &[f64], mu: f64, data: &[f64]) -> Vec<f64> {
+    let deviations: Vec<f64> = data.iter().map(|r| (r - mu).abs()).collect();
+    let start = deviations.iter().sum::<f64>() / deviations.len().max(1) as f64;
+    let mut sigmas = Vec::with_capacity(data.len());
+    for t in 0..data.len() {
+        let sigma = next_volatility(omega, arch, garch, &deviations[..t], &sigmas, start);
+        sigmas.push(sigma);
+    }
+    sigmas
+}
+
+fn quasi_log_likelihood(omega: f64, arch: &[f64], garch: &[f64], mu: f64, innovation: &AlphaStable, data: &[f64]) -> Result<f64, Error> {
+    let mut val = 0.0;
+    for (r, sigma) in data.iter().zip(volatilities(omega, arch, garch, mu, data)) {
+        val += innovation.pdf((r - mu) / sigma)?.ln() - sigma.ln();
+    }
+    Ok(val)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{StableArma, StableGarch};
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+
+    #[test]
+    fn test_residuals_recover_noise() {
+        let noise = AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        let model = StableArma::new(vec![0.5], vec![0.4], noise);
+        let data = [1.0, 2.0, 0.0, -1.0];
+        // e(1) = 2 - 0.5, e(2) = 0 - 1 - 0.4 e(1), e(3) = -1 - 0 - 0.4 e(2)
+        let e = model.residuals(&data);
+        let expected = [1.5, -1.6, -0.36];
+        for (x, y) in e.iter().zip(expected.iter()) {
+            assert!(close(*x, *y, 1e-14));
+        }
+    }
+
+    #[test]
+    fn test_fit_arma() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let noise = AlphaStable::new(1.6, 0.0, 1.0, 0.5).unwrap();
+        let model = StableArma::new(vec![0.6], vec![0.3], noise);
+        let data = model.simulate(300, &mut rng);
+        let fitted = StableArma::fit(&data, 1, 1).unwrap();
+        assert!(close(fitted.get_ar()[0], 0.6, 0.1));
+        assert!(close(fitted.get_ma()[0], 0.3, 0.1));
+        let (alpha, _, sigma, mu, _) = fitted.get_noise().get_params();
+        assert!(close(alpha, 1.6, 0.25));
+        assert!(close(sigma, 1.0, 0.15));
+        assert!(close(mu, 0.5, 0.3));
+        assert!(fitted.log_likelihood(&data).unwrap() >= model.log_likelihood(&data).unwrap());
+    }
+
+    #[test]
+    fn test_sample_size_error() {
+        assert!(matches!(StableArma::fit(&[0.0; 10], 1, 1), Err(Error::SampleSizeError { n: 10 })));
+    }
+
+    #[test]
+    fn test_garch_volatilities() {
+        let innovation = AlphaStable::new_S0(1.8, 0.0, 1.0, 0.0).unwrap();
+        let model = StableGarch::new_garch_1_1(0.1, 0.2, 0.5, 1.0, innovation).unwrap();
+        // Pre-sample values are the mean absolute deviation, 1
+        let sigmas = model.volatilities(&[2.0, -1.0, 1.0]);
+        let expected = [0.1 + 0.2 + 0.5, 0.1 + 0.2 * 1.0 + 0.5 * 0.8, 0.1 + 0.2 * 2.0 + 0.5 * 0.7];
+        for (x, y) in sigmas.iter().zip(expected.iter()) {
+            assert!(close(*x, *y, 1e-14));
+        }
+    }
+
+    #[test]
+    fn test_garch_parameter_error() {
+        let innovation = AlphaStable::new_S0(1.8, 0.0, 1.0, 0.0).unwrap();
+        assert!(matches!(StableGarch::new_garch_1_1(0.0, 0.1, 0.8, 0.0, innovation), Err(Error::GarchParameterError { .. })));
+        let innovation = AlphaStable::new_S0(1.8, 0.0, 1.0, 0.0).unwrap();
+        assert!(matches!(StableGarch::new(0.1, vec![-0.1], vec![], 0.0, innovation), Err(Error::GarchParameterError { .. })));
+    }
+
+    #[test]
+    fn test_fit_garch() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let innovation = AlphaStable::new_S0(1.8, 0.0, 1.0, 0.0).unwrap();
+        let model = StableGarch::new_garch_1_1(0.1, 0.15, 0.75, 0.2, innovation).unwrap();
+        let data = model.simulate(500, &mut rng);
+        let fitted = StableGarch::fit(&data, 1, 1).unwrap();
+        let (omega, arch, garch, mu) = fitted.get_params();
+        assert!(close(arch[0], 0.15, 0.08));
+        assert!(close(garch[0], 0.75, 0.15));
+        assert!(close(mu, 0.2, 0.15));
+        assert!(omega > 0.0);
+        let (alpha, _, _, _, _) = fitted.get_innovation().get_params();
+        assert!(close(alpha, 1.8, 0.2));
+    }
+}
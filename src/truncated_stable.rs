@@ -0,0 +1,173 @@
+use rand::Rng;
+
+use crate::alpha_stable::AlphaStable;
+use crate::error::Error;
+
+/// Defines an Alpha Stable distribution truncated to the interval [lower, upper].
+///
+/// Either bound may be infinite. The pdf, cdf and quantile are those of the wrapped distribution renormalised by the
+/// probability mass it places on [lower, upper].
+#[derive(Debug)]
+pub struct TruncatedStable {
+    distribution: AlphaStable,
+    lower: f64,
+    upper: f64,
+    cdf_lower: f64,
+    mass: f64,
+}
+
+// Smallest probability mass the truncation interval may carry.
+const MIN_MASS: f64 = 1e-8;
+
+// Samples are drawn by rejection from the wrapped distribution when the kept mass is at least this large, and by
+// inverting the cdf otherwise.
+const REJECTION_MASS: f64 = 0.25;
+
+impl TruncatedStable {
+
+    /// Create distribution by truncating an Alpha Stable distribution to [lower, upper].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alpha_stable::{AlphaStable, TruncatedStable};
+    ///
+    /// let distribution = TruncatedStable::new(AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap(), -5.0, 5.0).unwrap();
+    /// let val = distribution.pdf(0.5).unwrap();
+    /// ```
+    pub fn new(distribution: AlphaStable, lower: f64, upper: f64) -> Result<TruncatedStable, Error> {
+
+        if lower.is_nan() || upper.is_nan() || lower >= upper {
+            return Err(Error::TruncationBoundsError { lower, upper });
+        }
+
+        let cdf_lower = if lower == f64::NEG_INFINITY { 0.0 } else { distribution.cdf(lower)? };
+        let cdf_upper = if upper == f64::INFINITY { 1.0 } else { distribution.cdf(upper)? };
+        let mass = cdf_upper - cdf_lower;
+
+        if mass < MIN_MASS {
+            return Err(Error::NegligibleMassError { mass });
+        }
+
+        Ok(TruncatedStable { distribution, lower, upper, cdf_lower, mass })
+    }
+
+    /// Return the truncation bounds as tuple of (lower, upper).
+    pub fn get_bounds(&self) -> (f64, f64) {
+        (self.lower, self.upper)
+    }
+
+    /// Return the probability mass of the wrapped distribution on [lower, upper].
+    pub fn get_mass(&self) -> f64 {
+        self.mass
+    }
+
+    /// Return the wrapped distribution.
+    pub fn get_distribution(&self) -> &AlphaStable {
+        &self.distribution
+    }
+
+    /// Value of Probability Distribution function at x.
+    pub fn pdf(&self, x: f64) -> Result<f64, Error> {
+        if x < self.lower || x > self.upper {
+            return Ok(0.0);
+        }
+        Ok(self.distribution.pdf(x)? / self.mass)
+    }
+
+    /// Value of Cumulative Distribution function at x.
+    pub fn cdf(&self, x: f64) -> Result<f64, Error> {
+        if x <= self.lower {
+            return Ok(0.0);
+        }
+        if x >= self.upper {
+            return Ok(1.0);
+        }
+        Ok(((self.distribution.cdf(x)? - self.cdf_lower) / self.mass).clamp(0.0, 1.0))
+    }
+
+    /// Quantile function: the value of x at which the Cumulative Distribution function equals p.
+    pub fn quantile(&self, p: f64) -> Result<f64, Error> {
+        if p <= 0.0 || p >= 1.0 || p.is_nan() {
+            return Err(Error::ProbabilityError { p });
+        }
+        let x = self.distribution.quantile(self.cdf_lower + p * self.mass)?;
+        Ok(x.clamp(self.lower, self.upper))
+    }
+
+    /// Sample from the distribution.
+    ///
+    /// Uses rejection from the wrapped distribution when the kept mass is at least 1/4, and inversion of the
+    /// cdf otherwise, so the expected cost per sample is bounded whatever the truncation interval.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use alpha_stable::{AlphaStable, TruncatedStable};
+    ///
+    /// let mut rng = thread_rng();
+    /// let distribution = TruncatedStable::new(AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap(), -5.0, 5.0).unwrap();
+    /// let sample = distribution.sample(&mut rng).unwrap();
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<f64, Error> {
+        if self.mass >= REJECTION_MASS {
+            loop {
+                let x = self.distribution.sample(rng);
+                if x >= self.lower && x <= self.upper {
+                    return Ok(x);
+                }
+            }
+        }
+        let mut u = 0.0;
+        while u == 0.0 {
+            u = rng.gen::<f64>();
+        }
+        self.quantile(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::TruncatedStable;
+    use crate::alpha_stable::{close, AlphaStable};
+    use crate::error::Error;
+
+    #[test]
+    fn test_bounds_errors() {
+        let dist = || AlphaStable::new(1.5, 0.0, 1.0, 0.0).unwrap();
+        assert!(matches!(TruncatedStable::new(dist(), 1.0, 1.0), Err(Error::TruncationBoundsError { .. })));
+        assert!(matches!(TruncatedStable::new(dist(), 2.0, 1.0), Err(Error::TruncationBoundsError { .. })));
+        assert!(matches!(TruncatedStable::new(AlphaStable::new(2.0, 0.0, 1.0, 0.0).unwrap(), 20.0, 21.0), Err(Error::NegligibleMassError { .. })));
+    }
+
+    #[test]
+    fn test_cauchy() {
+        // Truncated Cauchy has closed form cdf
+        let dist = TruncatedStable::new(AlphaStable::new(1.0, 0.0, 1.0, 0.0).unwrap(), -1.0, 2.0).unwrap();
+        let f = |x: f64| 0.5 + x.atan() / std::f64::consts::PI;
+        let mass = f(2.0) - f(-1.0);
+        assert!(close(dist.get_mass(), mass, 1e-12));
+        assert!(close(dist.cdf(0.5).unwrap(), (f(0.5) - f(-1.0)) / mass, 1e-12));
+        assert!(close(dist.pdf(0.5).unwrap(), 1.0 / (std::f64::consts::PI * 1.25 * mass), 1e-12));
+        assert_eq!(dist.pdf(3.0).unwrap(), 0.0);
+        assert!(close(dist.cdf(dist.quantile(0.3).unwrap()).unwrap(), 0.3, 1e-9));
+    }
+
+    #[test]
+    fn test_sample_both_regimes() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for (lower, upper) in [(-1.0, 1.0), (3.0, 6.0)] {
+            let dist = TruncatedStable::new(AlphaStable::new(1.5, 0.5, 1.0, 0.0).unwrap(), lower, upper).unwrap();
+            let median = dist.quantile(0.5).unwrap();
+            let n = 400;
+            let below = (0..n).map(|_| dist.sample(&mut rng).unwrap())
+                .inspect(|x| assert!(*x >= lower && *x <= upper))
+                .filter(|x| *x < median)
+                .count();
+            assert!(close(below as f64 / n as f64, 0.5, 0.1));
+        }
+    }
+}